@@ -0,0 +1,302 @@
+//! Finding the saves folder among everywhere Steam may have put it.
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::{AoS2Env, vdf};
+
+/// Environment variable with the saves folder, for setups nothing else can find.
+pub const SAVES_DIR_VAR: &str = "AOS2_SAVES_DIR";
+/// Steam's id of the game, which also names its Proton prefix.
+pub const STEAM_APP_ID: &str = "390710";
+
+/// Where Steam may live on Linux, relative to home.
+///
+/// `.steam/steam` and `.steam/root` are usually symlinks to one of the others.
+const STEAM_ROOTS: [&str; 6] = [
+    ".local/share/Steam",
+    ".steam/steam",
+    ".steam/root",
+    ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+    ".var/app/com.valvesoftware.Steam/data/Steam",
+    "snap/steam/common/.local/share/Steam",
+];
+
+/// Looks for saves folders, from the environment or anything else for tests.
+#[derive(Debug, Clone, Default)]
+pub struct Discovery {
+    home: Option<PathBuf>,
+    saves_dir: Option<PathBuf>,
+}
+
+/// A place the saves folder may be in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub saves_folder: PathBuf,
+    pub reason: Reason,
+    pub exists: bool,
+}
+
+/// Why a place was considered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// Set with [`SAVES_DIR_VAR`].
+    Override,
+    /// The game's Proton prefix in a Steam library.
+    SteamLibrary { steam: PathBuf, library: PathBuf },
+    /// Where the game keeps saves by default on this system.
+    Usual,
+}
+
+impl Discovery {
+    /// Home folder and [`SAVES_DIR_VAR`] of the current user.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self {
+            home: std::env::home_dir(),
+            saves_dir: std::env::var_os(SAVES_DIR_VAR)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from),
+        }
+    }
+
+    #[must_use]
+    pub fn with_home(mut self, home: impl Into<PathBuf>) -> Self {
+        self.home = Some(home.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_saves_dir(mut self, saves_dir: impl Into<PathBuf>) -> Self {
+        self.saves_dir = Some(saves_dir.into());
+        self
+    }
+
+    /// Every place worth looking at, best first.
+    ///
+    /// The override always wins, then come folders that exist, in the order they were found.
+    #[must_use]
+    pub fn candidates(&self) -> Vec<Candidate> {
+        let mut found = Vec::new();
+        if let Some(saves_dir) = &self.saves_dir {
+            found.push((saves_dir.clone(), Reason::Override));
+        }
+        if let Some(home) = &self.home {
+            if cfg!(target_os = "linux") {
+                found.extend(steam_prefixes(home));
+            }
+            found.push((crate::saves_location(home), Reason::Usual));
+        }
+
+        let mut seen = Vec::new();
+        let mut candidates: Vec<Candidate> = found
+            .into_iter()
+            .filter(|(saves_folder, _)| {
+                // Symlinked Steam folders lead to the same place.
+                let key = saves_folder
+                    .canonicalize()
+                    .unwrap_or_else(|_| saves_folder.clone());
+                let is_new = !seen.contains(&key);
+                seen.push(key);
+                is_new
+            })
+            .map(|(saves_folder, reason)| Candidate {
+                exists: saves_folder.is_dir(),
+                saves_folder,
+                reason,
+            })
+            .collect();
+        candidates
+            .sort_by_key(|candidate| (candidate.reason != Reason::Override, !candidate.exists));
+        candidates
+    }
+
+    /// The first of [`Discovery::candidates`], if there's any place to look at all.
+    #[must_use]
+    pub fn best(&self) -> Option<AoS2Env> {
+        self.candidates()
+            .into_iter()
+            .next()
+            .map(|candidate| AoS2Env::from_path(candidate.saves_folder))
+    }
+}
+
+/// The game's prefix in every library of every Steam install.
+fn steam_prefixes(home: &Path) -> Vec<(PathBuf, Reason)> {
+    let mut prefixes = Vec::new();
+    for steam in STEAM_ROOTS.map(|root| home.join(root)) {
+        if !steam.is_dir() {
+            continue;
+        }
+
+        let listed = std::fs::read_to_string(steam.join("steamapps").join("libraryfolders.vdf"))
+            .map(|text| vdf::library_paths(&text))
+            .unwrap_or_default();
+        for library in std::iter::once(steam.clone()).chain(listed) {
+            prefixes.push((
+                proton_saves_location(&library),
+                Reason::SteamLibrary {
+                    steam: steam.clone(),
+                    library,
+                },
+            ));
+        }
+    }
+    prefixes
+}
+
+/// Saves folder inside the game's Proton prefix in a Steam library.
+pub fn proton_saves_location(library: impl AsRef<Path>) -> PathBuf {
+    library
+        .as_ref()
+        .join("steamapps")
+        .join("compatdata")
+        .join(STEAM_APP_ID)
+        .join("pfx")
+        .join("drive_c")
+        .join("users")
+        .join("steamuser")
+        .join("Documents")
+        .join("Fruitbat Factory")
+        .join("AoS2")
+}
+
+impl Display for Candidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            saves_folder,
+            reason,
+            exists,
+        } = self;
+        let state = if *exists { "found" } else { "missing" };
+        write!(f, "[{state}] {} ({reason})", saves_folder.display())
+    }
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::Override => write!(f, "set by `{SAVES_DIR_VAR}`"),
+            Reason::SteamLibrary { steam, library } if steam == library => {
+                write!(f, "Proton prefix of Steam in {}", steam.display())
+            }
+            Reason::SteamLibrary { steam, library } => write!(
+                f,
+                "Proton prefix in Steam library {}, listed by {}",
+                library.display(),
+                steam.display()
+            ),
+            Reason::Usual => f.write_str("usual location"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use tempfile::TempDir;
+
+    use super::{Discovery, Reason, proton_saves_location};
+
+    fn create_dir(home: &TempDir, path: impl AsRef<Path>) -> PathBuf {
+        let path = home.path().join(path);
+        std::fs::create_dir_all(&path).expect("Precondition: must create folder");
+        path
+    }
+
+    #[rstest::rstest]
+    fn flatpak_secondary_library_is_found_first() {
+        let home = tempfile::tempdir().expect("Precondition: must create home");
+        let steam = create_dir(&home, ".var/app/com.valvesoftware.Steam/.local/share/Steam");
+        let library = create_dir(&home, "games");
+        create_dir(&home, steam.join("steamapps"));
+        std::fs::write(
+            steam.join("steamapps/libraryfolders.vdf"),
+            format!(
+                "\"libraryfolders\" {{ \"0\" {{ \"path\" \"{}\" }} \"1\" {{ \"path\" \"{}\" }} }}",
+                steam.display(),
+                library.display()
+            ),
+        )
+        .expect("Precondition: must write library folders");
+        std::fs::create_dir_all(proton_saves_location(&library))
+            .expect("Precondition: must create saves");
+
+        let candidates = Discovery::default().with_home(home.path()).candidates();
+
+        let best = &candidates[0];
+        assert!(best.exists);
+        assert_eq!(proton_saves_location(&library), best.saves_folder);
+        assert_eq!(
+            Reason::SteamLibrary {
+                steam: steam.clone(),
+                library
+            },
+            best.reason
+        );
+        assert!(
+            candidates
+                .iter()
+                .all(|candidate| candidate.exists == (candidate == best)),
+            "Only one folder exists"
+        );
+    }
+
+    #[rstest::rstest]
+    fn symlinked_steam_is_listed_once() {
+        let home = tempfile::tempdir().expect("Precondition: must create home");
+        let steam = create_dir(&home, ".local/share/Steam");
+        create_dir(&home, ".steam");
+        std::os::unix::fs::symlink(&steam, home.path().join(".steam/steam"))
+            .expect("Precondition: must link");
+        std::fs::create_dir_all(proton_saves_location(&steam))
+            .expect("Precondition: must create saves");
+
+        let candidates = Discovery::default().with_home(home.path()).candidates();
+
+        let n_found = candidates
+            .iter()
+            .filter(|candidate| candidate.exists)
+            .count();
+        assert_eq!(1, n_found);
+        assert_eq!(proton_saves_location(&steam), candidates[0].saves_folder);
+    }
+
+    #[rstest::rstest]
+    fn override_always_wins() {
+        let home = tempfile::tempdir().expect("Precondition: must create home");
+        let steam = create_dir(&home, ".local/share/Steam");
+        std::fs::create_dir_all(proton_saves_location(&steam))
+            .expect("Precondition: must create saves");
+
+        let discovery = Discovery::default()
+            .with_home(home.path())
+            .with_saves_dir("/nowhere/AoS2");
+
+        let candidates = discovery.candidates();
+        assert_eq!(Reason::Override, candidates[0].reason);
+        assert!(!candidates[0].exists);
+        assert_eq!(
+            Some(PathBuf::from("/nowhere/AoS2")),
+            discovery.best().map(|env| env.saves_folder)
+        );
+    }
+
+    #[rstest::rstest]
+    fn nothing_found_falls_back_to_usual_location() {
+        let home = tempfile::tempdir().expect("Precondition: must create home");
+
+        let candidates = Discovery::default().with_home(home.path()).candidates();
+
+        assert_eq!(1, candidates.len());
+        assert_eq!(Reason::Usual, candidates[0].reason);
+        assert_eq!(
+            crate::saves_location(home.path()),
+            candidates[0].saves_folder
+        );
+    }
+}
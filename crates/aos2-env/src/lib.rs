@@ -1,6 +1,10 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 
+pub mod discovery;
+pub mod process;
+mod vdf;
+
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -11,6 +15,8 @@ pub const EXAMPLE_HOME: &str = "/home/<user>";
 #[cfg(target_os = "macos")]
 pub const EXAMPLE_HOME: &str = "/Users/<user>";
 
+pub const EDITOR_FOLDER_NAME: &str = "AoS2 Save Editor";
+
 #[derive(Debug, Clone)]
 pub struct AoS2Env {
     pub saves_folder: PathBuf,
@@ -18,16 +24,17 @@ pub struct AoS2Env {
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error("Home directory is not defined")]
+    #[error(
+        "Home directory is not defined, and neither is `{}`",
+        discovery::SAVES_DIR_VAR
+    )]
     Home,
 }
 
 impl AoS2Env {
+    /// The best place [`discovery::Discovery`] finds for the current user.
     pub fn from_home_dir() -> Result<Self, Error> {
-        std::env::home_dir()
-            .map(saves_location)
-            .map(|saves_folder| Self { saves_folder })
-            .ok_or(Error::Home)
+        discovery::Discovery::from_env().best().ok_or(Error::Home)
     }
 
     pub fn from_path(path: impl Into<PathBuf>) -> Self {
@@ -35,6 +42,14 @@ impl AoS2Env {
             saves_folder: path.into(),
         }
     }
+
+    /// Folder for the editor's own files (backups, etc.), right next to the saves folder.
+    ///
+    /// The game doesn't know about it, so it never touches it.
+    #[must_use]
+    pub fn editor_folder(&self) -> PathBuf {
+        self.saves_folder.with_file_name(EDITOR_FOLDER_NAME)
+    }
 }
 
 #[cfg(target_os = "windows")]
@@ -48,21 +63,8 @@ pub fn saves_location(home: impl AsRef<Path>) -> PathBuf {
 #[cfg(target_os = "linux")]
 pub fn saves_location(home: impl AsRef<Path>) -> PathBuf {
     // This is the cringe location where I had it.
-    // Not sure if it's universal enough but "it works on my machine". xd.
-    home.as_ref()
-        .join(".local")
-        .join("share")
-        .join("Steam")
-        .join("steamapps")
-        .join("compatdata")
-        .join("390710")
-        .join("pfx")
-        .join("drive_c")
-        .join("users")
-        .join("steamuser")
-        .join("Documents")
-        .join("Fruitbat Factory")
-        .join("AoS2")
+    // Other Steam installs and libraries are up to [`discovery`].
+    discovery::proton_saves_location(home.as_ref().join(".local").join("share").join("Steam"))
 }
 
 /// Note: This is a crutch to just make it compile for `MacOS`.
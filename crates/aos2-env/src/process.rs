@@ -0,0 +1,144 @@
+//! Finding out whether the game is running, so its files aren't written under its feet.
+
+use std::path::PathBuf;
+
+/// The game's executable, under the same name in Proton.
+pub const GAME_EXE_NAME: &str = "AoS2.exe";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Process {
+    pub pid: u32,
+    /// Program and its arguments.
+    pub command_line: Vec<String>,
+}
+
+/// Where running processes come from, so tests can make some up.
+pub trait ProcessList {
+    fn processes(&self) -> Vec<Process>;
+}
+
+/// Processes as Linux shows them in `/proc`.
+///
+/// Systems without it have nothing there, as if the game never runs.
+#[derive(Debug, Clone)]
+pub struct ProcFs {
+    root: PathBuf,
+}
+
+impl Process {
+    /// Either the game itself, or Wine/Proton running it.
+    #[must_use]
+    pub fn is_game(&self) -> bool {
+        self.command_line.iter().any(|argument| {
+            let name = argument.rsplit(['/', '\\']).next().unwrap_or_default();
+            name.eq_ignore_ascii_case(GAME_EXE_NAME)
+        })
+    }
+}
+
+impl ProcFs {
+    pub fn at(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl Default for ProcFs {
+    fn default() -> Self {
+        Self::at("/proc")
+    }
+}
+
+impl ProcessList for ProcFs {
+    fn processes(&self) -> Vec<Process> {
+        let Ok(entries) = std::fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+
+        entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let pid = entry.file_name().to_str()?.parse().ok()?;
+                // Processes may exit while being listed.
+                let command_line = std::fs::read(entry.path().join("cmdline")).ok()?;
+                Some(Process {
+                    pid,
+                    command_line: split_command_line(&command_line),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The first process that looks like the game.
+pub fn find_game(processes: &(impl ProcessList + ?Sized)) -> Option<Process> {
+    processes.processes().into_iter().find(Process::is_game)
+}
+
+/// Arguments in `/proc/<pid>/cmdline` end with a zero byte each.
+fn split_command_line(raw: &[u8]) -> Vec<String> {
+    raw.split(|&byte| byte == 0)
+        .filter(|argument| !argument.is_empty())
+        .map(|argument| String::from_utf8_lossy(argument).into_owned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProcFs, Process, ProcessList, find_game};
+
+    struct Stub(Vec<Process>);
+
+    impl ProcessList for Stub {
+        fn processes(&self) -> Vec<Process> {
+            self.0.clone()
+        }
+    }
+
+    fn process(pid: u32, command_line: &[&str]) -> Process {
+        Process {
+            pid,
+            command_line: command_line.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::proton(&[
+        "/home/user/.local/share/Steam/steamapps/common/Proton 9.0/dist/bin/wine64",
+        "Z:\\home\\user\\.local\\share\\Steam\\steamapps\\common\\Acceleration of SUGURI 2\\AoS2.exe",
+    ])]
+    #[case::windows(&["C:\\Program Files (x86)\\Steam\\steamapps\\common\\Acceleration of SUGURI 2\\aos2.exe"])]
+    #[case::wine_path(&["/games/Acceleration of SUGURI 2/AoS2.exe"])]
+    fn game_is_found(#[case] command_line: &[&str]) {
+        let processes = Stub(vec![process(1, &["/sbin/init"]), process(42, command_line)]);
+
+        assert_eq!(Some(42), find_game(&processes).map(|game| game.pid));
+    }
+
+    #[rstest::rstest]
+    #[case::editor(&["./aos2-save-editor", "set", "title", "AoS2.exe.backup"])]
+    #[case::folder(&["ls", "/games/AoS2.exe/"])]
+    fn other_processes_are_not_the_game(#[case] command_line: &[&str]) {
+        let processes = Stub(vec![process(7, command_line)]);
+
+        assert_eq!(None, find_game(&processes));
+    }
+
+    #[rstest::rstest]
+    fn proc_fs_reads_command_lines() {
+        let proc = tempfile::tempdir().expect("Precondition: must create proc");
+        let root = proc.path();
+        for (pid, cmdline) in [
+            ("12", b"wine\0C:\\AoS2.exe\0".as_slice()),
+            ("13", b"bash\0"),
+        ] {
+            std::fs::create_dir_all(root.join(pid)).expect("Precondition: must create");
+            std::fs::write(root.join(pid).join("cmdline"), cmdline)
+                .expect("Precondition: must write");
+        }
+        std::fs::create_dir_all(root.join("self")).expect("Precondition: must create");
+
+        let game = find_game(&ProcFs::at(root));
+
+        assert_eq!(Some(process(12, &["wine", "C:\\AoS2.exe"])), game);
+    }
+}
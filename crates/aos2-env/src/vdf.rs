@@ -0,0 +1,141 @@
+//! Just enough of Valve's text `KeyValues` format to read `libraryfolders.vdf`.
+
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Text(String),
+    Open,
+    Close,
+}
+
+/// Every Steam library folder listed in the file.
+///
+/// Newer files have `"path"` keys in each library,
+/// older ones list paths right under numbered keys.
+pub fn library_paths(text: &str) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    let mut key: Option<String> = None;
+    let mut depth = 0_usize;
+    for token in tokens(text) {
+        match (token, key.take()) {
+            (Token::Text(value), Some(key)) => {
+                // Numbered keys deeper down are app ids in `apps`.
+                let is_path = key.eq_ignore_ascii_case("path")
+                    || (depth == 1 && key.chars().all(|c| c.is_ascii_digit()));
+                if is_path {
+                    paths.push(PathBuf::from(value));
+                }
+            }
+            (Token::Text(text), None) => key = Some(text),
+            // Keys before braces name sections, not values.
+            (Token::Open, _) => depth += 1,
+            (Token::Close, _) => depth = depth.saturating_sub(1),
+        }
+    }
+    paths
+}
+
+fn tokens(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '"' => {
+                let mut text = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some(other) => text.push(other),
+                            None => (),
+                        },
+                        other => text.push(other),
+                    }
+                }
+                tokens.push(Token::Text(text));
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => (),
+            c => {
+                let mut text = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '{' | '}' | '"') {
+                        break;
+                    }
+                    text.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Text(text));
+            }
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::library_paths;
+
+    #[rstest::rstest]
+    fn paths_are_read_from_current_format() {
+        let text = r#"
+"libraryfolders"
+{
+	"0"
+	{
+		"path"		"/home/user/.local/share/Steam"
+		"label"		"path"
+		"contentid"		"4242"
+		"apps"
+		{
+			"228980"		"181500000"
+		}
+	}
+	"1"
+	{
+		"path"		"/mnt/games/Steam Library"
+		"apps"
+		{
+			"390710"		"1370000000"
+		}
+	}
+}
+"#;
+
+        assert_eq!(
+            vec![
+                PathBuf::from("/home/user/.local/share/Steam"),
+                PathBuf::from("/mnt/games/Steam Library"),
+            ],
+            library_paths(text)
+        );
+    }
+
+    #[rstest::rstest]
+    fn paths_are_read_from_old_format() {
+        let text = r#"
+"LibraryFolders"
+{
+	// Written by an old Steam client.
+	"TimeNextStatsReport"		"1600000000"
+	"ContentStatsID"		"-4242"
+	"1"		"D:\\SteamLibrary"
+}
+"#;
+
+        assert_eq!(vec![PathBuf::from("D:\\SteamLibrary")], library_paths(text));
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+/// Part of a damaged file that didn't make sense and was replaced to read the rest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Anomaly {
+    pub offset: u64,
+    pub field: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Anomaly {
+    /// Shows bytes like hex editors do, e.g. `a4 00 00 00`.
+    #[must_use]
+    pub fn hex(bytes: &[u8]) -> String {
+        bytes
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Display for Anomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            offset,
+            field,
+            expected,
+            actual,
+        } = self;
+        write!(
+            f,
+            "0x{offset:02x} `{field}`: expected {expected}, got {actual}"
+        )
+    }
+}
+
+/// Where the file stopped making sense, if it's a format error at all.
+///
+/// IO errors and custom errors (like unsupported versions) have nothing to fix in place.
+#[must_use]
+pub fn format_error_position(error: &binrw::Error) -> Option<u64> {
+    match error {
+        binrw::Error::BadMagic { pos, .. }
+        | binrw::Error::AssertFail { pos, .. }
+        | binrw::Error::EnumErrors { pos, .. }
+        | binrw::Error::NoVariantMatch { pos } => Some(*pos),
+        binrw::Error::Backtrace(backtrace) => format_error_position(&backtrace.error),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Anomaly;
+
+    #[rstest::rstest]
+    fn anomaly_shows_offset_and_bytes() {
+        let anomaly = Anomaly {
+            offset: 4,
+            field: "_body_length".to_owned(),
+            expected: Anomaly::hex(&[0xa4, 0, 0, 0]),
+            actual: Anomaly::hex(&[0, 0, 0, 0]),
+        };
+
+        assert_eq!(
+            "0x04 `_body_length`: expected a4 00 00 00, got 00 00 00 00",
+            anomaly.to_string()
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    io::{Cursor, Write},
+    path::{Path, PathBuf},
+};
+
+use binrw::{BinWrite, meta::WriteEndian};
+
+use crate::Error;
+
+/// Replaces an existing file either completely or not at all.
+///
+/// The value is serialized in memory first, then written to a sibling temp file,
+/// which is flushed to disk and renamed over the original.
+/// A crash or a full disk leaves the original file untouched.
+///
+/// Like the game, refuses to create files that don't exist yet.
+pub fn write_atomically<T>(path: &Path, value: &T) -> Result<(), Error>
+where
+    T: BinWrite + WriteEndian,
+    for<'a> <T as BinWrite>::Args<'a>: Default,
+{
+    replace_existing(path, &to_bytes(path, value)?)
+}
+
+/// Opposite of [`write_atomically`]: only makes new files, along with missing folders.
+///
+/// An existing file is never touched.
+pub fn create_atomically<T>(path: &Path, value: &T) -> Result<(), Error>
+where
+    T: BinWrite + WriteEndian,
+    for<'a> <T as BinWrite>::Args<'a>: Default,
+{
+    create_new(path, &to_bytes(path, value)?)
+}
+
+/// [`write_atomically`] for bytes that are already serialized.
+pub(crate) fn replace_existing(path: &Path, bytes: &[u8]) -> Result<(), Error> {
+    std::fs::metadata(path).map_err(|err| Error::writing_file(path, err))?;
+    replace(path, bytes)
+}
+
+/// [`create_atomically`] for bytes that are already serialized.
+pub(crate) fn create_new(path: &Path, bytes: &[u8]) -> Result<(), Error> {
+    if path.exists() {
+        let err = std::io::Error::from(std::io::ErrorKind::AlreadyExists);
+        return Err(Error::writing_file(path, err));
+    }
+    if let Some(folder) = path.parent() {
+        std::fs::create_dir_all(folder).map_err(|err| Error::writing_file(path, err))?;
+    }
+    replace(path, bytes)
+}
+
+fn to_bytes<T>(path: &Path, value: &T) -> Result<Vec<u8>, Error>
+where
+    T: BinWrite + WriteEndian,
+    for<'a> <T as BinWrite>::Args<'a>: Default,
+{
+    let mut bytes = Cursor::new(Vec::new());
+    BinWrite::write(value, &mut bytes).map_err(|err| Error::writing_binary(path, err))?;
+    Ok(bytes.into_inner())
+}
+
+/// Puts bytes at `path` either completely or not at all, whether there's a file or not.
+///
+/// For the editor's own files and copies of save files, which have errors of their own.
+pub fn replace_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let temp_path = temp_path(path);
+    let result = write_synced(&temp_path, bytes).and_then(|()| std::fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn replace(path: &Path, bytes: &[u8]) -> Result<(), Error> {
+    replace_atomically(path, bytes).map_err(|err| Error::writing_file(path, err))
+}
+
+fn write_synced(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".tmp");
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ErrorDetail, UnknownU8};
+
+    use super::{create_atomically, replace_atomically, temp_path, write_atomically};
+
+    #[rstest::rstest]
+    fn replaces_existing_file() {
+        let dir = tempfile::tempdir().expect("Precondition: must create temp dir");
+        let path = dir.path().join("file.bin");
+        std::fs::write(&path, b"old contents").expect("Precondition");
+
+        write_atomically(&path, &UnknownU8::new(0x42)).expect("Must write");
+
+        assert_eq!(vec![0x42], std::fs::read(&path).expect("Must exist"));
+        assert!(!temp_path(&path).exists());
+    }
+
+    #[rstest::rstest]
+    fn does_not_create_missing_file() {
+        let dir = tempfile::tempdir().expect("Precondition: must create temp dir");
+        let path = dir.path().join("file.bin");
+
+        let error = write_atomically(&path, &UnknownU8::new(0x42)).expect_err("Must fail");
+
+        assert!(matches!(error.detail, ErrorDetail::NotFound));
+        assert!(!path.exists());
+        assert!(!temp_path(&path).exists());
+    }
+
+    #[rstest::rstest]
+    fn creates_missing_file_and_folders() {
+        let dir = tempfile::tempdir().expect("Precondition: must create temp dir");
+        let path = dir.path().join("nested").join("file.bin");
+
+        create_atomically(&path, &UnknownU8::new(0x42)).expect("Must create");
+
+        assert_eq!(vec![0x42], std::fs::read(&path).expect("Must exist"));
+    }
+
+    #[rstest::rstest]
+    fn does_not_overwrite_existing_file_on_create() {
+        let dir = tempfile::tempdir().expect("Precondition: must create temp dir");
+        let path = dir.path().join("file.bin");
+        std::fs::write(&path, b"old contents").expect("Precondition");
+
+        create_atomically(&path, &UnknownU8::new(0x42)).expect_err("Must fail");
+
+        assert_eq!(
+            b"old contents".as_slice(),
+            std::fs::read(&path).expect("Must exist")
+        );
+    }
+
+    #[rstest::rstest]
+    fn raw_bytes_replace_or_create() {
+        let dir = tempfile::tempdir().expect("Precondition: must create temp dir");
+        let existing = dir.path().join("existing.bin");
+        let missing = dir.path().join("missing.bin");
+        std::fs::write(&existing, b"old contents").expect("Precondition");
+
+        replace_atomically(&existing, b"new").expect("Must replace");
+        replace_atomically(&missing, b"new").expect("Must create");
+
+        assert_eq!(
+            b"new".to_vec(),
+            std::fs::read(&existing).expect("Must exist")
+        );
+        assert_eq!(
+            b"new".to_vec(),
+            std::fs::read(&missing).expect("Must exist")
+        );
+        assert!(!temp_path(&existing).exists());
+    }
+}
@@ -0,0 +1,128 @@
+//! What every save file can do, whatever its layout or encryption.
+
+use std::{
+    io::{Cursor, Read, Seek, Write},
+    path::Path,
+};
+
+use aos2_env::AoS2Env;
+use binrw::BinResult;
+
+use crate::{Error, atomic};
+
+/// A file the game keeps in its saves folder.
+///
+/// Only reading and writing are up to each format, files and folders are handled here.
+pub trait SaveFormat: Sized {
+    /// Name of the file in the saves folder.
+    const FILE_NAME: &'static str;
+
+    fn read_from<R: Read + Seek>(reader: &mut R) -> BinResult<Self>;
+
+    fn write_to<W: Write + Seek>(&self, writer: &mut W) -> BinResult<()>;
+
+    /// Contents exactly as they are on disk.
+    fn from_bytes(bytes: &[u8]) -> BinResult<Self> {
+        Self::read_from(&mut Cursor::new(bytes))
+    }
+
+    /// Opposite of [`SaveFormat::from_bytes`].
+    fn to_bytes(&self) -> BinResult<Vec<u8>> {
+        let mut writer = Cursor::new(Vec::new());
+        self.write_to(&mut writer)?;
+        Ok(writer.into_inner())
+    }
+
+    fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|err| Error::reading_file(path, err))?;
+        Self::from_bytes(&bytes).map_err(|err| Error::reading_binary(path, err))
+    }
+
+    /// Replaces an existing file, see [`crate::write_atomically`].
+    fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let bytes = self
+            .to_bytes()
+            .map_err(|err| Error::writing_binary(path, err))?;
+        atomic::replace_existing(path, &bytes)
+    }
+
+    /// Makes a new file, refusing to replace an existing one.
+    fn create_file(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let bytes = self
+            .to_bytes()
+            .map_err(|err| Error::writing_binary(path, err))?;
+        atomic::create_new(path, &bytes)
+    }
+
+    fn load(env: &AoS2Env) -> Result<Self, Error> {
+        Self::from_file(env.saves_folder.join(Self::FILE_NAME))
+    }
+
+    fn save(&self, env: &AoS2Env) -> Result<(), Error> {
+        self.save_to_file(env.saves_folder.join(Self::FILE_NAME))
+    }
+
+    fn create(&self, env: &AoS2Env) -> Result<(), Error> {
+        self.create_file(env.saves_folder.join(Self::FILE_NAME))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Seek, Write};
+
+    use aos2_env::AoS2Env;
+    use binrw::{BinRead, BinResult, BinWrite};
+
+    use crate::ErrorDetail;
+
+    use super::SaveFormat;
+
+    /// Stores its byte inverted, like a (very) encrypted file.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Inverted(u8);
+
+    impl SaveFormat for Inverted {
+        const FILE_NAME: &'static str = "inverted.bin";
+
+        fn read_from<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
+            let byte = u8::read_le(reader)?;
+            Ok(Self(!byte))
+        }
+
+        fn write_to<W: Write + Seek>(&self, writer: &mut W) -> BinResult<()> {
+            (!self.0).write_le(writer)
+        }
+    }
+
+    #[rstest::rstest]
+    fn bytes_roundtrip() {
+        let bytes = Inverted(0x0f).to_bytes().expect("Must write");
+
+        assert_eq!(vec![0xf0], bytes);
+        assert_eq!(
+            Inverted(0x0f),
+            Inverted::from_bytes(&bytes).expect("Must read")
+        );
+    }
+
+    #[rstest::rstest]
+    fn files_are_created_saved_and_loaded_in_saves_folder() {
+        let dir = tempfile::tempdir().expect("Precondition: must create temp dir");
+        let folder = dir.path().join("AoS2");
+        let env = AoS2Env::from_path(&folder);
+
+        let missing = Inverted(0x01).save(&env).expect_err("Must not create");
+        Inverted(0x01).create(&env).expect("Must create");
+        Inverted(0x02).save(&env).expect("Must replace");
+        let loaded = Inverted::load(&env).expect("Must load");
+        let on_disk = std::fs::read(folder.join(Inverted::FILE_NAME)).expect("Must exist");
+
+        assert!(matches!(missing.detail, ErrorDetail::NotFound));
+        assert_eq!(Inverted(0x02), loaded);
+        assert_eq!(vec![!0x02], on_disk);
+    }
+}
@@ -1,6 +1,14 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 
+mod anomaly;
+mod atomic;
+mod format;
+
+pub use self::anomaly::{Anomaly, format_error_position};
+pub use self::atomic::{create_atomically, replace_atomically, write_atomically};
+pub use self::format::SaveFormat;
+
 use std::path::PathBuf;
 
 /// Means the purpose of the field is unknown.
@@ -8,6 +16,11 @@ use std::path::PathBuf;
 /// "Explicit is better than implicit".
 #[binrw::binrw]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, derive_more::From)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[brw(little)]
 pub struct UnknownU8(u8);
 
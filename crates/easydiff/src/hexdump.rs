@@ -0,0 +1,58 @@
+use std::fmt::Display;
+
+use player_progress::raw::{self, Field};
+
+/// Decrypted `game.sys`, byte by byte, with known fields next to them.
+#[derive(Debug, Clone)]
+pub struct ProgressHexDump {
+    encrypted: Vec<u8>,
+    decrypted: Vec<u8>,
+    layout: Vec<Field>,
+}
+
+impl ProgressHexDump {
+    pub fn new(encrypted: Vec<u8>) -> anyhow::Result<Self> {
+        let decrypted = raw::decrypt(&encrypted)?;
+        Ok(Self {
+            encrypted,
+            decrypted,
+            layout: raw::layout(),
+        })
+    }
+}
+
+impl Display for ProgressHexDump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "| {:<6} | {:<9} | {:<9} | {:<5} | Field",
+            "Offset", "Encrypted", "Decrypted", "ASCII"
+        )?;
+
+        for (offset, (&encrypted, &decrypted)) in
+            self.encrypted.iter().zip(&self.decrypted).enumerate()
+        {
+            let field = self
+                .layout
+                .iter()
+                .find(|field| field.offsets.contains(&offset));
+            let name = match field {
+                Some(field) if field.offsets.start == offset => field.name.clone(),
+                Some(_) => "  ...".to_owned(),
+                None => format!("?? _{offset:#04x}"),
+            };
+            let ascii = if decrypted.is_ascii_graphic() {
+                char::from(decrypted)
+            } else {
+                '.'
+            };
+
+            writeln!(
+                f,
+                "| {offset:#06x} | {encrypted:#04x}      | {decrypted:#04x}      | {ascii:<5} | {name}",
+            )?;
+        }
+
+        Ok(())
+    }
+}
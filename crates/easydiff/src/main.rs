@@ -1,26 +1,46 @@
 mod diff;
 mod fmt;
+mod hexdump;
 mod path;
+mod structured;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use aos2_env::AoS2Env;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 use crate::{
     diff::{BinaryFile, FileDifference},
+    hexdump::ProgressHexDump,
     path::CanonicalSaveFilePath,
+    structured::StructuredDifference,
 };
 
 /// Looks for files in AoS2 saves folder and shows a simple difference.
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
     /// The original (unchanged) file.
-    #[arg(value_name = "ORIGINAL")]
-    before: PathBuf,
+    #[arg(value_name = "ORIGINAL", required = true)]
+    before: Option<PathBuf>,
     /// The other (modified) file.
-    #[arg(value_name = "MODIFIED")]
-    after: PathBuf,
+    #[arg(value_name = "MODIFIED", required = true)]
+    after: Option<PathBuf>,
+    /// Parse `game.sys` or `player.rkg` and compare fields instead of raw bytes.
+    #[arg(long)]
+    structured: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print decrypted `game.sys` bytes with known field names next to them.
+    Hexdump {
+        /// Progress file to look at.
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
 }
 
 fn main() -> anyhow::Result<()> {
@@ -31,11 +51,21 @@ fn main() -> anyhow::Result<()> {
 }
 
 fn run(aos2_env: AoS2Env, args: Args) -> anyhow::Result<()> {
-    let before_path = CanonicalSaveFilePath::new(&aos2_env.saves_folder, &args.before)?;
-    let after_path = CanonicalSaveFilePath::new(&aos2_env.saves_folder, &args.after)?;
+    let (before, after) = match args {
+        Args {
+            command: Some(Command::Hexdump { file }),
+            ..
+        } => return hexdump(&aos2_env, &file),
+        Args {
+            before: Some(before),
+            after: Some(after),
+            ..
+        } => (before, after),
+        Args { .. } => anyhow::bail!("Two files are required to compare them"),
+    };
 
-    let before = BinaryFile::load(&before_path)?;
-    let after = BinaryFile::load(&after_path)?;
+    let before_path = CanonicalSaveFilePath::new(&aos2_env.saves_folder, &before)?;
+    let after_path = CanonicalSaveFilePath::new(&aos2_env.saves_folder, &after)?;
 
     println!(
         "Working with files:\nBefore: {}\nAfter: {}\n",
@@ -43,7 +73,28 @@ fn run(aos2_env: AoS2Env, args: Args) -> anyhow::Result<()> {
         after_path.display(),
     );
 
+    if args.structured {
+        println!(
+            "{}",
+            StructuredDifference::between(&before_path, &after_path)?
+        );
+        return Ok(());
+    }
+
+    let before = BinaryFile::load(&before_path)?;
+    let after = BinaryFile::load(&after_path)?;
+
     println!("{}", FileDifference::between(&before, &after));
 
     Ok(())
 }
+
+fn hexdump(aos2_env: &AoS2Env, file: &Path) -> anyhow::Result<()> {
+    let path = CanonicalSaveFilePath::new(&aos2_env.saves_folder, file)?;
+    let file = BinaryFile::load(&path)?;
+
+    println!("Working with file: {}\n", path.display());
+    println!("{}", ProgressHexDump::new(file.content().to_vec())?);
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use std::{fmt::Display, path::Path};
+
+use anyhow::Context;
+use binary_file::SaveFormat;
+use online_profile::PlayerOnlineProfile;
+use player_progress::PlayerProgress;
+use serde_json::Value;
+
+/// Field-level difference between two parsed save files.
+///
+/// Changes in unknown fields (`_0x..`, `unused_0x..`) are listed first,
+/// because that's where all the reverse-engineering happens.
+#[derive(Debug, Clone)]
+pub struct StructuredDifference {
+    unknown: Vec<FieldDifference>,
+    known: Vec<FieldDifference>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDifference {
+    pub path: String,
+    pub previous: Value,
+    pub current: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SaveFileKind {
+    Progress,
+    Profile,
+}
+
+impl StructuredDifference {
+    pub fn between(previous: &Path, current: &Path) -> anyhow::Result<Self> {
+        let kind = SaveFileKind::recognize(previous)?;
+        anyhow::ensure!(
+            kind == SaveFileKind::recognize(current)?,
+            "Can't compare different kinds of save files"
+        );
+
+        let differences = FieldDifference::between(&kind.parse(previous)?, &kind.parse(current)?);
+        let (unknown, known) = differences
+            .into_iter()
+            .partition(FieldDifference::is_unknown);
+        Ok(Self { unknown, known })
+    }
+}
+
+impl FieldDifference {
+    pub fn between(previous: &Value, current: &Value) -> Vec<Self> {
+        let mut differences = Vec::new();
+        Self::collect(String::new(), previous, current, &mut differences);
+        differences
+    }
+
+    fn collect(path: String, previous: &Value, current: &Value, out: &mut Vec<Self>) {
+        match (previous, current) {
+            (Value::Object(previous), Value::Object(current)) => {
+                for (key, previous_value) in previous {
+                    let field = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    let current_value = current.get(key).unwrap_or(&Value::Null);
+                    Self::collect(field, previous_value, current_value, out);
+                }
+            }
+            (Value::Array(previous), Value::Array(current)) => {
+                if previous.len() != current.len() {
+                    out.push(Self {
+                        path: format!("{path}.length"),
+                        previous: previous.len().into(),
+                        current: current.len().into(),
+                    });
+                }
+                for (index, (previous, current)) in previous.iter().zip(current).enumerate() {
+                    Self::collect(format!("{path}[{index}]"), previous, current, out);
+                }
+            }
+            (previous, current) if previous != current => out.push(Self {
+                path,
+                previous: previous.clone(),
+                current: current.clone(),
+            }),
+            _ => (),
+        }
+    }
+
+    /// Fields nobody figured out yet.
+    pub fn is_unknown(&self) -> bool {
+        let name = self.path.rsplit('.').next().unwrap_or_default();
+        name.starts_with("_0x") || name.starts_with("unused_0x")
+    }
+}
+
+impl SaveFileKind {
+    fn recognize(path: &Path) -> anyhow::Result<Self> {
+        let file_name = path.file_name().and_then(|name| name.to_str());
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        match (file_name, extension) {
+            (Some(PlayerProgress::FILE_NAME), _) | (_, Some("sys")) => Ok(Self::Progress),
+            (Some(PlayerOnlineProfile::FILE_NAME), _) | (_, Some("rkg")) => Ok(Self::Profile),
+            _ => anyhow::bail!(
+                "Unknown save file `{}` (expected `{}` or `{}`)",
+                path.display(),
+                PlayerProgress::FILE_NAME,
+                PlayerOnlineProfile::FILE_NAME
+            ),
+        }
+    }
+
+    fn parse(self, path: &Path) -> anyhow::Result<Value> {
+        let value = match self {
+            SaveFileKind::Progress => serde_json::to_value(PlayerProgress::from_file(path)?),
+            SaveFileKind::Profile => serde_json::to_value(PlayerOnlineProfile::from_file(path)?),
+        };
+        value.with_context(|| format!("Failed to represent `{}`", path.display()))
+    }
+}
+
+impl Display for StructuredDifference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { unknown, known } = self;
+        if unknown.is_empty() && known.is_empty() {
+            return writeln!(f, "Identical files");
+        }
+
+        if !unknown.is_empty() {
+            writeln!(f, "!! UNKNOWN FIELDS CHANGED !!")?;
+            for difference in unknown {
+                writeln!(f, ">> {difference}")?;
+            }
+            writeln!(f)?;
+        }
+
+        if !known.is_empty() {
+            writeln!(f, "Known fields changed:")?;
+            for difference in known {
+                writeln!(f, "   {difference}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for FieldDifference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let show = |value: &Value| match value {
+            Value::String(text) if text.is_empty() => "\"\"".to_owned(),
+            Value::String(text) => text.clone(),
+            Value::Number(number) if self.is_unknown() => number
+                .as_u64()
+                .map_or_else(|| number.to_string(), |byte| format!("{byte:#04x}")),
+            other => other.to_string(),
+        };
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.path,
+            show(&self.previous),
+            show(&self.current)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::FieldDifference;
+
+    #[rstest::rstest]
+    fn finds_nested_changes() {
+        let previous = json!({ "arenas": { "unlocked": { "sora": "disabled" } }, "_0x1e": 0 });
+        let current = json!({ "arenas": { "unlocked": { "sora": "enabled" } }, "_0x1e": 3 });
+
+        let differences: Vec<String> = FieldDifference::between(&previous, &current)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            vec![
+                "arenas.unlocked.sora: disabled -> enabled".to_owned(),
+                "_0x1e: 0x00 -> 0x03".to_owned(),
+            ],
+            differences
+        );
+    }
+
+    #[rstest::rstest]
+    fn list_items_are_indexed() {
+        let previous = json!({ "titles": ["locked", "locked"] });
+        let current = json!({ "titles": ["locked", "open"] });
+
+        let differences = FieldDifference::between(&previous, &current);
+
+        assert_eq!(1, differences.len());
+        assert_eq!("titles[1]", differences[0].path);
+        assert!(!differences[0].is_unknown());
+    }
+
+    #[rstest::rstest]
+    fn list_length_change_is_reported() {
+        let previous = json!({ "titles": ["locked", "locked"] });
+        let current = json!({ "titles": ["open"] });
+
+        let differences: Vec<String> = FieldDifference::between(&previous, &current)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            vec![
+                "titles.length: 2 -> 1".to_owned(),
+                "titles[0]: locked -> open".to_owned(),
+            ],
+            differences
+        );
+    }
+}
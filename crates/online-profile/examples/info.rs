@@ -1,4 +1,5 @@
 use aos2_env::AoS2Env;
+use binary_file::SaveFormat;
 use online_profile::PlayerOnlineProfile;
 
 fn main() -> anyhow::Result<()> {
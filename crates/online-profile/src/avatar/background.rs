@@ -13,6 +13,11 @@
 #[brw(little, repr(u32))]
 #[repr(u32)]
 #[try_from(repr)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum Background {
     #[default]
     LightBlue = 0x00,
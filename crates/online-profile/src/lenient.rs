@@ -0,0 +1,403 @@
+//! Reading damaged `player.rkg` files, as much of them as possible.
+//!
+//! Fields are read one by one, so a bad byte doesn't take the rest of the file with it.
+//! Whatever doesn't make sense gets the value of a fresh profile,
+//! and every replacement is reported, so nothing changes silently.
+//! A cut off file is reported once, at the first missing field.
+
+use std::{io::Cursor, ops::RangeInclusive, path::Path};
+
+use binary_file::{Anomaly, UnknownU8};
+use binrw::{BinRead, BinWrite};
+
+use crate::{
+    LobbyName, LobbyPassword, Nickname, PlayerOnlineProfile,
+    unlocks::{AvatarsSection, BackgroundsSection, Status, TitlesSection},
+    version::{KnownLayout, KnownVersion, Layout, Version},
+};
+
+/// What's left of a damaged file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Salvaged {
+    pub profile: PlayerOnlineProfile,
+    pub anomalies: Vec<Anomaly>,
+}
+
+/// Fails only when the file can't be read at all.
+pub fn from_file(path: impl AsRef<Path>) -> Result<Salvaged, binary_file::Error> {
+    let path = path.as_ref();
+    let bytes =
+        std::fs::read(path).map_err(|error| binary_file::Error::reading_file(path, error))?;
+    Ok(from_bytes(&bytes))
+}
+
+#[must_use]
+pub fn from_bytes(bytes: &[u8]) -> Salvaged {
+    let mut reader = Reader {
+        bytes,
+        position: 0,
+        is_cut_off: false,
+        anomalies: Vec::new(),
+    };
+
+    let version = reader.version();
+    // The version doesn't tell the layout, so broken lengths get the latest one.
+    let layout = KnownLayout::CURRENT.sizes();
+    let sizes = |size: fn(Layout) -> usize| -> Vec<usize> {
+        let mut sizes: Vec<usize> = KnownLayout::members()
+            .into_iter()
+            .map(|known| size(known.sizes()))
+            .collect();
+        sizes.dedup();
+        sizes
+    };
+
+    let profile = PlayerOnlineProfile {
+        version: version.unwrap_or_default(),
+        _0x02: reader.field::<UnknownU8>("_0x02", 1),
+        _0x03: reader.field::<UnknownU8>("_0x03", 1),
+        country: reader.flag("country"),
+        nickname: reader.text::<Nickname>("nickname", Nickname::LENGTH_RANGE),
+        lobby_name: reader.text::<LobbyName>("lobby_name", LobbyName::LENGTH_RANGE),
+        lobby_password: reader.text::<LobbyPassword>("lobby_password", LobbyPassword::LENGTH_RANGE),
+        avatar_character: reader.field("avatar_character", 4),
+        avatar_background: reader.field("avatar_background", 4),
+        unlockable_avatars: AvatarsSection::from_items(reader.section(
+            "unlockable_avatars",
+            &sizes(|layout| layout.avatars),
+            layout.avatars,
+        )),
+        unlockable_backgrounds: BackgroundsSection::from_items(reader.section(
+            "unlockable_backgrounds",
+            &sizes(|layout| layout.backgrounds),
+            layout.backgrounds,
+        )),
+        title_character_in_background: reader.field("title_character_in_background", 4),
+        title_text_id: reader.field("title_text_id", 4),
+        titles: TitlesSection::from_items(reader.section(
+            "titles",
+            &sizes(|layout| layout.titles),
+            layout.titles,
+        )),
+        ingame_title: reader.flag("ingame_title"),
+        hitstun_meter: reader.flag("hitstun_meter"),
+        spectators: reader.flag("spectators"),
+        title_color: reader.field("title_color", 4),
+    };
+    reader.trailing();
+
+    Salvaged {
+        profile,
+        anomalies: reader.anomalies,
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    is_cut_off: bool,
+    anomalies: Vec<Anomaly>,
+}
+
+impl<'a> Reader<'a> {
+    fn version(&mut self) -> Option<Version> {
+        let bytes = self.take("version", 2, || known_versions().join(" or "))?;
+        let version = Version::read(&mut Cursor::new(bytes)).ok();
+        if version.is_none() {
+            self.report(
+                0,
+                "version",
+                known_versions().join(" or "),
+                Anomaly::hex(bytes),
+            );
+        }
+        version
+    }
+
+    /// Enums and other fields of a fixed size.
+    fn field<T>(&mut self, name: &str, size: usize) -> T
+    where
+        T: for<'b> BinRead<Args<'b> = ()> + for<'b> BinWrite<Args<'b> = ()> + Default,
+    {
+        self.fixed(name, size, || {
+            format!("a known value like {}", fresh_hex::<T>())
+        })
+    }
+
+    /// Yes/no bytes, like visibility and unlocks.
+    fn flag<T>(&mut self, name: &str) -> T
+    where
+        T: for<'b> BinRead<Args<'b> = ()> + Default,
+    {
+        self.fixed(name, 1, || "00 or 01".to_owned())
+    }
+
+    fn fixed<T>(&mut self, name: &str, size: usize, expected: impl Fn() -> String) -> T
+    where
+        T: for<'b> BinRead<Args<'b> = ()> + Default,
+    {
+        let offset = self.position;
+        let Some(bytes) = self.take(name, size, &expected) else {
+            return T::default();
+        };
+        if let Ok(value) = T::read_le(&mut Cursor::new(bytes)) {
+            return value;
+        }
+
+        self.report(offset, name, expected(), Anomaly::hex(bytes));
+        T::default()
+    }
+
+    /// Length-prefixed ASCII text.
+    fn text<T>(&mut self, name: &str, lengths: RangeInclusive<usize>) -> T
+    where
+        T: for<'b> BinRead<Args<'b> = ()> + TryFrom<String> + Default,
+    {
+        let offset = self.position;
+        let mut cursor = Cursor::new(&self.bytes[offset..]);
+        if let Ok(text) = T::read_le(&mut cursor) {
+            self.position += usize::try_from(cursor.position()).unwrap_or_default();
+            return text;
+        }
+
+        let expected = format!("{}-{} ASCII characters", lengths.start(), lengths.end());
+        let Some(length) = self.take(name, 4, || expected.clone()) else {
+            return T::default();
+        };
+        let length = u32::from_le_bytes(length.try_into().unwrap_or_default()) as usize;
+
+        match self.bytes.get(self.position..self.position + length) {
+            Some(chars) if lengths.contains(&length) => {
+                // Length is fine, the characters aren't.
+                self.position += length;
+                self.report(offset, name, expected, Anomaly::hex(chars));
+                T::default()
+            }
+            _ => {
+                // Length is garbage, so the text ends where printable characters do.
+                let chars: String = self.bytes[self.position..]
+                    .iter()
+                    .take(*lengths.end())
+                    .take_while(|byte| byte.is_ascii_graphic() || **byte == b' ')
+                    .map(|&byte| char::from(byte))
+                    .collect();
+                self.position += chars.len();
+                self.report(offset, name, expected, format!("length {length}"));
+                T::try_from(chars).unwrap_or_default()
+            }
+        }
+    }
+
+    /// Unlocks, with a length any known layout may have.
+    ///
+    /// A broken length is replaced with the fallback one.
+    fn section(&mut self, name: &str, lengths: &[usize], fallback: usize) -> Vec<Status> {
+        let offset = self.position;
+        let expected = || {
+            lengths
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" or ")
+        };
+        let length = match self.take(&format!("{name} length"), 4, expected) {
+            Some(bytes) => u32::from_le_bytes(bytes.try_into().unwrap_or_default()) as usize,
+            None => fallback,
+        };
+        let length = if lengths.contains(&length) {
+            length
+        } else {
+            self.report(
+                offset,
+                &format!("{name} length"),
+                expected(),
+                length.to_string(),
+            );
+            fallback
+        };
+
+        (0..length)
+            .map(|index| self.flag(&format!("{name}[{index}]")))
+            .collect()
+    }
+
+    fn trailing(&mut self) {
+        let extra = self.bytes.len().saturating_sub(self.position);
+        if extra > 0 {
+            self.report(
+                self.position,
+                "end of file",
+                "nothing".to_owned(),
+                format!("{extra} more bytes"),
+            );
+        }
+    }
+
+    fn take(
+        &mut self,
+        name: &str,
+        size: usize,
+        expected: impl FnOnce() -> String,
+    ) -> Option<&'a [u8]> {
+        if let Some(bytes) = self.bytes.get(self.position..self.position + size) {
+            self.position += size;
+            return Some(bytes);
+        }
+
+        if !self.is_cut_off {
+            self.is_cut_off = true;
+            self.report(self.position, name, expected(), "end of file".to_owned());
+        }
+        self.position = self.bytes.len();
+        None
+    }
+
+    fn report(&mut self, offset: usize, name: &str, expected: String, actual: String) {
+        self.anomalies.push(Anomaly {
+            offset: offset as u64,
+            field: name.to_owned(),
+            expected,
+            actual,
+        });
+    }
+}
+
+fn known_versions() -> Vec<String> {
+    KnownVersion::members()
+        .into_iter()
+        .map(|known| Anomaly::hex(&known.raw().to_le_bytes()))
+        .collect()
+}
+
+fn fresh_hex<T>() -> String
+where
+    T: for<'b> BinWrite<Args<'b> = ()> + Default,
+{
+    let mut writer = Cursor::new(Vec::new());
+    match T::default().write_le(&mut writer) {
+        Ok(()) => Anomaly::hex(&writer.into_inner()),
+        Err(_) => "a fresh value".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, path::PathBuf};
+
+    use binary_file::SaveFormat;
+    use binrw::BinWrite;
+
+    use crate::{PlayerOnlineProfile, Visibility, avatar, unlocks::Status, version::KnownLayout};
+
+    use super::from_bytes;
+
+    const CRATE_ROOT: &str = env!("CARGO_MANIFEST_DIR");
+
+    fn bytes_of(profile: &PlayerOnlineProfile) -> Vec<u8> {
+        let mut writer = Cursor::new(Vec::new());
+        profile.write(&mut writer).expect("Must write to a buffer");
+        writer.into_inner()
+    }
+
+    #[rstest::rstest]
+    fn intact_file_has_no_anomalies() {
+        let input_file = PathBuf::from(CRATE_ROOT).join("test_inputs/player-generic-0.rkg");
+        let bytes = std::fs::read(input_file).expect("Precondition: must read test input");
+        let expected = PlayerOnlineProfile::from_file(
+            PathBuf::from(CRATE_ROOT).join("test_inputs/player-generic-0.rkg"),
+        )
+        .expect("Precondition: must parse");
+
+        let salvaged = from_bytes(&bytes);
+
+        assert_eq!(Vec::<binary_file::Anomaly>::new(), salvaged.anomalies);
+        assert_eq!(expected, salvaged.profile);
+    }
+
+    #[rstest::rstest]
+    fn bad_bytes_are_replaced_and_the_rest_is_kept() {
+        let original = PlayerOnlineProfile {
+            avatar_character: avatar::Character::Alte,
+            spectators: Visibility::Show,
+            ..Default::default()
+        };
+        let mut bytes = bytes_of(&original);
+        // Country, right after the version and two unknown bytes.
+        bytes[0x04] = 0x07;
+
+        let salvaged = from_bytes(&bytes);
+
+        assert_eq!(1, salvaged.anomalies.len());
+        assert_eq!(0x04, salvaged.anomalies[0].offset);
+        assert_eq!("country", salvaged.anomalies[0].field);
+        assert_eq!("07", salvaged.anomalies[0].actual);
+        assert_eq!(original, salvaged.profile);
+    }
+
+    #[rstest::rstest]
+    fn wrong_section_length_is_replaced() {
+        let original = PlayerOnlineProfile::default();
+        let mut bytes = bytes_of(&original);
+        // Fresh nickname and lobby name are `Suguri`, the password is empty.
+        let avatars_at = 0x05 + (4 + 6) + (4 + 6) + 4 + 4 + 4;
+        bytes[avatars_at..avatars_at + 4].copy_from_slice(&0xdead_u32.to_le_bytes());
+
+        let salvaged = from_bytes(&bytes);
+
+        let fields: Vec<&str> = salvaged
+            .anomalies
+            .iter()
+            .map(|anomaly| anomaly.field.as_str())
+            .collect();
+        assert_eq!(vec!["unlockable_avatars length"], fields);
+        assert_eq!(original, salvaged.profile);
+    }
+
+    #[rstest::rstest]
+    fn cut_off_file_is_reported_once() {
+        let bytes = bytes_of(&PlayerOnlineProfile::default());
+
+        let salvaged = from_bytes(&bytes[..0x40]);
+
+        assert_eq!(1, salvaged.anomalies.len());
+        assert_eq!("end of file", salvaged.anomalies[0].actual);
+    }
+
+    #[rstest::rstest]
+    fn damaged_unlocks_keep_the_nickname() {
+        let mut original = PlayerOnlineProfile {
+            nickname: "Survivor"
+                .to_owned()
+                .try_into()
+                .expect("Precondition: valid"),
+            ..Default::default()
+        };
+        original.unlockable_backgrounds.as_mut()[2] = Status::Locked;
+        let mut bytes = bytes_of(&original);
+        let sizes = KnownLayout::CURRENT.sizes();
+        // Lobby name is `Suguri`, the password is empty.
+        let avatars_at = 0x05 + (4 + 8) + (4 + 6) + 4 + 4 + 4;
+        let status_at = avatars_at + 4 + sizes.avatars + 4 + 2;
+        let titles_at = avatars_at + 4 + sizes.avatars + 4 + sizes.backgrounds + 4 + 4;
+        bytes[status_at] = 0x07;
+        bytes[titles_at..titles_at + 4].copy_from_slice(&0xdead_u32.to_le_bytes());
+
+        let salvaged = from_bytes(&bytes);
+
+        let found: Vec<(usize, &str, &str)> = salvaged
+            .anomalies
+            .iter()
+            .map(|anomaly| {
+                let offset = usize::try_from(anomaly.offset).expect("Must fit");
+                (offset, anomaly.field.as_str(), anomaly.actual.as_str())
+            })
+            .collect();
+        let expected = vec![
+            (status_at, "unlockable_backgrounds[2]", "07"),
+            (titles_at, "titles length", "57005"),
+        ];
+        assert_eq!(expected, found);
+        assert_eq!("Survivor", salvaged.profile.nickname.to_string());
+        assert_eq!(original, salvaged.profile);
+    }
+}
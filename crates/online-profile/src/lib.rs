@@ -2,10 +2,10 @@
 #![allow(clippy::missing_errors_doc)]
 
 pub mod avatar;
+pub mod lenient;
 pub mod title;
 pub mod unlocks;
-
-mod version;
+pub mod version;
 
 // Re-export;
 mod text;
@@ -14,13 +14,16 @@ pub use crate::text::lobby_name::LobbyName;
 pub use crate::text::lobby_password::LobbyPassword;
 pub use crate::text::nickname::Nickname;
 
-use aos2_env::AoS2Env;
-use binary_file::UnknownU8;
+use std::io::{Read, Seek, Write};
+
+use binary_file::{SaveFormat, UnknownU8};
+use binrw::{BinRead, BinResult, BinWrite};
 
-use crate::version::Version;
+use crate::version::{KnownLayout, Layout, Version};
 
 #[binrw::binrw]
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[brw(little)]
 pub struct PlayerOnlineProfile {
     pub version: Version,
@@ -48,53 +51,92 @@ pub struct PlayerOnlineProfile {
 }
 
 #[binrw::binrw]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    derive_more::Display,
+    enum_array::EnumMembersArray,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 #[brw(little)]
 pub enum Visibility {
+    #[display("shown")]
     #[brw(magic = 0x01u8)]
     Show,
     #[default]
+    #[display("hidden")]
     #[brw(magic = 0x00u8)]
     Hide,
 }
 
 impl PlayerOnlineProfile {
-    pub const FILE_NAME: &'static str = "player.rkg";
+    /// Actual sizes of unlock sections.
+    #[must_use]
+    pub fn layout(&self) -> Layout {
+        Layout {
+            avatars: self.unlockable_avatars.len(),
+            backgrounds: self.unlockable_backgrounds.len(),
+            titles: self.titles.len(),
+        }
+    }
 
-    pub fn load(env: &AoS2Env) -> Result<Self, binary_file::Error> {
-        Self::from_file(env.saves_folder.join(Self::FILE_NAME))
+    /// Known layout with the same section sizes, if any.
+    #[must_use]
+    pub fn known_layout(&self) -> Option<KnownLayout> {
+        KnownLayout::detect(self.layout())
     }
 
-    pub fn save(&self, env: &AoS2Env) -> Result<(), binary_file::Error> {
-        self.save_to_file(env.saves_folder.join(Self::FILE_NAME))
+    /// Whether the file looks like the one the latest game version makes.
+    #[must_use]
+    pub fn is_up_to_date(&self) -> bool {
+        self.version == Version::current() && self.known_layout() == Some(KnownLayout::CURRENT)
     }
 
-    pub fn from_file<P>(path: P) -> Result<Self, binary_file::Error>
-    where
-        P: AsRef<std::path::Path>,
-        for<'a> <Self as binrw::BinRead>::Args<'a>: Default,
-    {
-        let mut reader = std::fs::File::open(path.as_ref())
-            .map_err(|err| binary_file::Error::reading_file(path.as_ref(), err))?;
+    /// Brings an older profile to the current version and layout.
+    ///
+    /// New unlocks are added as locked, existing ones are kept.
+    pub fn migrate(&mut self) {
+        let Layout {
+            avatars,
+            backgrounds,
+            titles,
+        } = KnownLayout::CURRENT.sizes();
 
-        <Self as binrw::BinRead>::read(&mut reader)
-            .map_err(|err| binary_file::Error::reading_binary(path.as_ref(), err))
+        self.version = Version::current();
+        self.unlockable_avatars.grow_to(avatars);
+        self.unlockable_backgrounds.grow_to(backgrounds);
+        self.titles.grow_to(titles);
     }
+}
+
+impl SaveFormat for PlayerOnlineProfile {
+    const FILE_NAME: &'static str = "player.rkg";
 
-    pub fn save_to_file<P>(&self, path: P) -> Result<(), binary_file::Error>
-    where
-        P: AsRef<std::path::Path>,
-        for<'a> <Self as binrw::BinWrite>::Args<'a>: Default,
-    {
-        let mut writer = std::fs::File::options()
-            .create(false)
-            .write(true)
-            .truncate(true)
-            .open(path.as_ref())
-            .map_err(|err| binary_file::Error::writing_file(path.as_ref(), err))?;
-
-        <Self as binrw::BinWrite>::write(self, &mut writer)
-            .map_err(|err| binary_file::Error::writing_binary(path.as_ref(), err))
+    fn read_from<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
+        <Self as BinRead>::read(reader)
+    }
+
+    fn write_to<W: Write + Seek>(&self, writer: &mut W) -> BinResult<()> {
+        <Self as BinWrite>::write(self, writer)
+    }
+}
+
+impl Visibility {
+    #[must_use]
+    pub fn toggle(self) -> Self {
+        match self {
+            Visibility::Show => Visibility::Hide,
+            Visibility::Hide => Visibility::Show,
+        }
     }
 }
 
@@ -103,9 +145,11 @@ mod tests {
     use std::io::Cursor;
     use std::path::PathBuf;
 
-    use binrw::BinRead;
+    use binary_file::SaveFormat;
+    use binrw::{BinRead, BinWrite};
 
     use super::PlayerOnlineProfile;
+    use crate::version::{KnownLayout, Version};
 
     const CRATE_ROOT: &str = env!("CARGO_MANIFEST_DIR");
 
@@ -142,6 +186,19 @@ mod tests {
         PlayerOnlineProfile::read(&mut cursor).expect("Must parse");
     }
 
+    #[rstest::rstest]
+    fn default_profile_is_readable_and_up_to_date() {
+        let mut bytes = Cursor::new(Vec::new());
+        PlayerOnlineProfile::default()
+            .write(&mut bytes)
+            .expect("Must write to a buffer");
+        bytes.set_position(0);
+
+        let profile = PlayerOnlineProfile::read(&mut bytes).expect("Must parse");
+
+        assert!(profile.is_up_to_date());
+    }
+
     #[rstest::rstest]
     #[case("player-generic-0.rkg")]
     #[case("player-generic-1.rkg")]
@@ -153,4 +210,73 @@ mod tests {
         let f = PlayerOnlineProfile::from_file(input_file).expect("Must parse");
         assert_eq!(f.version, crate::version::Version::current());
     }
+
+    #[cfg(feature = "serde")]
+    #[rstest::rstest]
+    #[case("player-generic-0.rkg")]
+    // `player-generic-1.rkg` is damaged and doesn't parse at all.
+    #[case("player-generic-2.rkg")]
+    fn generic_file_serde_roundtrip(#[case] file_name: &str) {
+        let input_file = PathBuf::from(CRATE_ROOT)
+            .join("test_inputs")
+            .join(file_name);
+        let expected = std::fs::read(&input_file).expect("Precondition: must read test input");
+        let profile =
+            PlayerOnlineProfile::read(&mut Cursor::new(expected.clone())).expect("Must parse");
+
+        let json = serde_json::to_string(&profile).expect("Must serialize to JSON");
+        let from_json: PlayerOnlineProfile =
+            serde_json::from_str(&json).expect("Must read JSON back");
+        let toml = toml::to_string(&profile).expect("Must serialize to TOML");
+        let from_toml: PlayerOnlineProfile = toml::from_str(&toml).expect("Must read TOML back");
+
+        for deserialized in [from_json, from_toml] {
+            let mut writer = Cursor::new(Vec::new());
+            deserialized
+                .write(&mut writer)
+                .expect("Must write to a buffer");
+
+            assert_eq!(expected, writer.into_inner());
+        }
+    }
+
+    #[rstest::rstest]
+    fn up_to_date_profile_is_not_migrated() {
+        let input_file = PathBuf::from(CRATE_ROOT).join("test_inputs/player-generic-0.rkg");
+        let original = PlayerOnlineProfile::from_file(input_file).expect("Must parse");
+
+        let mut migrated = original.clone();
+        migrated.migrate();
+
+        assert!(original.is_up_to_date());
+        assert_eq!(original, migrated);
+    }
+
+    /// Both real files say 1.9.2, yet their sections differ.
+    #[rstest::rstest]
+    #[case("player-generic-0.rkg", KnownLayout::Latest)]
+    #[case("player-generic-2.rkg", KnownLayout::Early)]
+    fn layout_is_detected_from_real_files(#[case] file_name: &str, #[case] expected: KnownLayout) {
+        let input_file = PathBuf::from(CRATE_ROOT)
+            .join("test_inputs")
+            .join(file_name);
+        let profile = PlayerOnlineProfile::from_file(input_file).expect("Must parse");
+
+        assert_eq!(Version::current(), profile.version);
+        assert_eq!(Some(expected), profile.known_layout());
+    }
+
+    #[rstest::rstest]
+    fn older_layout_is_migrated() {
+        let input_file = PathBuf::from(CRATE_ROOT).join("test_inputs/player-generic-2.rkg");
+        let original = PlayerOnlineProfile::from_file(input_file).expect("Must parse");
+
+        let mut migrated = original.clone();
+        migrated.migrate();
+
+        assert!(!original.is_up_to_date());
+        assert!(migrated.is_up_to_date());
+        let kept = original.titles.len();
+        assert_eq!(original.titles.as_ref(), &migrated.titles.as_ref()[..kept]);
+    }
 }
@@ -2,6 +2,11 @@ use std::ops::RangeInclusive;
 
 #[binrw::binrw]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, derive_more::Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
 #[brw(little)]
 #[br(try_map = |encoded: EncodedText| encoded.try_into())]
 #[bw(map = EncodedText::from)]
@@ -54,6 +59,12 @@ impl<const MIN: u8, const MAX: u8> TryFrom<String> for AsciiText<MIN, MAX> {
     }
 }
 
+impl<const MIN: u8, const MAX: u8> From<AsciiText<MIN, MAX>> for String {
+    fn from(AsciiText(text): AsciiText<MIN, MAX>) -> Self {
+        text
+    }
+}
+
 impl<const MIN: u8, const MAX: u8> TryFrom<EncodedText> for AsciiText<MIN, MAX> {
     type Error = Error<MIN, MAX>;
 
@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use super::ascii_text;
 
 type Text = ascii_text::AsciiText<0, 24>;
@@ -5,9 +7,18 @@ type Error = ascii_text::Error<0, 24>;
 
 #[binrw::binrw]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, derive_more::From, derive_more::Display)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[brw(little)]
 pub struct LobbyName(Text);
 
+impl LobbyName {
+    pub const LENGTH_RANGE: RangeInclusive<usize> = Text::LENGTH_RANGE;
+}
+
 impl TryFrom<String> for LobbyName {
     type Error = Error;
 
@@ -1,5 +1,28 @@
+//! Lists of online unlocks, as long as the game wants them to be.
+//!
+//! An item with id `N` sits at index `N` of its section:
+//! [`Character::Sora`](crate::avatar::Character::Sora) (`0x01`) is the second avatar.
+//! Sections are shorter than the enums, so some items never fit:
+//!
+//! - [`avatar::Character`] from `0x21` on, including `Invisible` (`0xff`).
+//! - [`avatar::Background`] from `0x13` on, including the silhouette one (`0xff`).
+//! - [`title::Text::Disabled`] (`0xffff_ffff`).
+//!
+//! Those give [`Error::OutOfRange`] instead of a panic, unless indexed directly.
+//! Sizes above are from the latest layout, older profiles are shorter still,
+//! see [`KnownLayout`].
+
+use std::ops::{Index, IndexMut};
+
+use crate::{avatar, title, version::KnownLayout};
+
 #[binrw::binrw]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 #[brw(little)]
 pub enum Status {
     #[brw(magic = 0x01u8)]
@@ -9,10 +32,42 @@ pub enum Status {
     Locked,
 }
 
+impl Status {
+    #[must_use]
+    pub fn is_open(self) -> bool {
+        match self {
+            Status::Open => true,
+            Status::Locked => false,
+        }
+    }
+}
+
+impl std::ops::Not for Status {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        match self {
+            Status::Open => Status::Locked,
+            Status::Locked => Status::Open,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    #[error("Item `{id:#x}` doesn't fit in a section of {length} items")]
+    OutOfRange { id: u32, length: usize },
+}
+
 macro_rules! declare_sized_section {
-    ($name:ident, DEFAULT_SIZE = $size:expr) => {
+    ($name:ident, item = $item:ty, DEFAULT_SIZE = $size:expr) => {
         #[binrw::binrw]
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
         #[brw(little)]
         pub struct $name {
             #[bw(try_calc = items.len().try_into())]
@@ -22,6 +77,10 @@ macro_rules! declare_sized_section {
         }
 
         impl $name {
+            pub(crate) fn from_items(items: Vec<Status>) -> Self {
+                Self { items }
+            }
+
             #[must_use]
             pub fn is_fully_unlocked(&self) -> bool {
                 let Self { items } = self;
@@ -34,6 +93,91 @@ macro_rules! declare_sized_section {
                     *item = Status::Open;
                 }
             }
+
+            #[must_use]
+            pub fn len(&self) -> usize {
+                let Self { items } = self;
+                items.len()
+            }
+
+            #[must_use]
+            pub fn is_empty(&self) -> bool {
+                let Self { items } = self;
+                items.is_empty()
+            }
+
+            pub fn get(&self, item: $item) -> Result<Status, Error> {
+                let Self { items } = self;
+                let index = Self::index_of(item, items.len())?;
+                Ok(items[index])
+            }
+
+            pub fn set(&mut self, item: $item, status: Status) -> Result<(), Error> {
+                let Self { items } = self;
+                let index = Self::index_of(item, items.len())?;
+                items[index] = status;
+                Ok(())
+            }
+
+            /// Known items with their statuses, in file order.
+            ///
+            /// Items the editor has no name for yet are skipped.
+            pub fn iter(&self) -> impl Iterator<Item = ($item, Status)> + '_ {
+                let Self { items } = self;
+                items.iter().enumerate().filter_map(|(index, &status)| {
+                    let id = u32::try_from(index).ok()?;
+                    let item = <$item>::try_from(id).ok()?;
+                    Some((item, status))
+                })
+            }
+
+            fn index_of(item: $item, length: usize) -> Result<usize, Error> {
+                let id = item as u32;
+                usize::try_from(id)
+                    .ok()
+                    .filter(|&index| index < length)
+                    .ok_or(Error::OutOfRange { id, length })
+            }
+
+            /// Adds locked items at the end, never removes any.
+            pub fn grow_to(&mut self, len: usize) {
+                let Self { items } = self;
+                if items.len() < len {
+                    items.resize(len, Status::Locked);
+                }
+            }
+        }
+
+        /// Panics if the item doesn't fit, see [`Self::get`] for a fallible version.
+        impl Index<$item> for $name {
+            type Output = Status;
+
+            fn index(&self, item: $item) -> &Self::Output {
+                let Self { items } = self;
+                &items[item as usize]
+            }
+        }
+
+        /// Panics if the item doesn't fit, see [`Self::set`] for a fallible version.
+        impl IndexMut<$item> for $name {
+            fn index_mut(&mut self, item: $item) -> &mut Self::Output {
+                let Self { items } = self;
+                &mut items[item as usize]
+            }
+        }
+
+        impl AsRef<[Status]> for $name {
+            fn as_ref(&self) -> &[Status] {
+                let Self { items } = self;
+                items
+            }
+        }
+
+        impl AsMut<[Status]> for $name {
+            fn as_mut(&mut self) -> &mut [Status] {
+                let Self { items } = self;
+                items
+            }
         }
 
         impl Default for $name {
@@ -46,6 +190,66 @@ macro_rules! declare_sized_section {
     };
 }
 
-declare_sized_section!(TitlesSection, DEFAULT_SIZE = 0x01_11);
-declare_sized_section!(AvatarsSection, DEFAULT_SIZE = 0x1f);
-declare_sized_section!(BackgroundsSection, DEFAULT_SIZE = 0x13);
+// Fresh profiles are made with the latest layout, like the game does.
+declare_sized_section!(
+    TitlesSection,
+    item = title::Text,
+    DEFAULT_SIZE = KnownLayout::CURRENT.sizes().titles
+);
+declare_sized_section!(
+    AvatarsSection,
+    item = avatar::Character,
+    DEFAULT_SIZE = KnownLayout::CURRENT.sizes().avatars
+);
+declare_sized_section!(
+    BackgroundsSection,
+    item = avatar::Background,
+    DEFAULT_SIZE = KnownLayout::CURRENT.sizes().backgrounds
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{avatar, title};
+
+    use super::{AvatarsSection, BackgroundsSection, Error, Status, TitlesSection};
+
+    #[rstest::rstest]
+    fn item_sits_at_its_id() {
+        let mut avatars = AvatarsSection::default();
+        avatars[avatar::Character::Sora] = Status::Open;
+
+        assert_eq!(Status::Open, avatars.as_ref()[0x01]);
+        assert_eq!(Ok(Status::Open), avatars.get(avatar::Character::Sora));
+    }
+
+    #[rstest::rstest]
+    fn special_items_do_not_fit() {
+        let backgrounds = BackgroundsSection::default();
+        let mut titles = TitlesSection::default();
+
+        assert_eq!(
+            Err(Error::OutOfRange {
+                id: 0xff,
+                length: 0x13
+            }),
+            backgrounds.get(avatar::Background::LightGrayBackgroundWithSilhouette)
+        );
+        assert!(titles.set(title::Text::Disabled, Status::Open).is_err());
+    }
+
+    #[rstest::rstest]
+    fn iteration_follows_the_file() {
+        let mut avatars = AvatarsSection::default();
+        avatars
+            .set(avatar::Character::Alte, Status::Open)
+            .expect("Must fit");
+
+        let open: Vec<_> = avatars
+            .iter()
+            .filter_map(|(item, status)| status.is_open().then_some(item))
+            .collect();
+
+        assert_eq!(vec![avatar::Character::Alte], open);
+        assert_eq!(avatars.len(), avatars.iter().count());
+    }
+}
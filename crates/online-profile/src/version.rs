@@ -3,24 +3,79 @@ use std::fmt::Display;
 use binary_file::UnsupportedVersion;
 use binrw::{BinRead, BinResult};
 
+use crate::{avatar, title};
+
 /// Online profile version parsed by the game.
 ///
 /// | Game Version | File Version |
 /// |:------------:|:------------:|
-/// |     1.9.2    |     A2_05    |
-/// |     1.9.1    |     A1_05    |
+/// |     1.9.2    |    `A2_05`   |
+/// |     1.9.1    |    `A1_05`   |
+///
+/// Only versions from [`KnownVersion`] can be parsed.
 #[binrw::binrw]
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[brw(little)]
 pub struct Version(#[br(parse_with = Version::parse_raw_num)] u16);
 
+/// Versions the editor knows how to read.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, enum_array::EnumMembersArray,
+)]
+pub enum KnownVersion {
+    V1_9_1,
+    V1_9_2,
+}
+
+/// Sizes of unlock sections, as found in a file.
+///
+/// The game doesn't resize sections of older profiles,
+/// so the version alone doesn't tell the layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Layout {
+    pub avatars: usize,
+    pub backgrounds: usize,
+    pub titles: usize,
+}
+
+/// Layouts seen in real profiles, told apart by section lengths.
+///
+/// | Layout   | Avatars | Backgrounds | Titles  | Seen in                        |
+/// |:--------:|:-------:|:-----------:|:-------:|:-------------------------------|
+/// | `Early`  |  `0x1f` |    `0x13`   | `0x111` | 1.9.2 profiles made long ago   |
+/// | `Latest` |  `0x21` |    `0x13`   | `0x11d` | 1.9.2 profiles made by 1.9.2   |
+///
+/// Both come with the 1.9.2 version number,
+/// no 1.9.1 file has been seen to tell what it had.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, enum_array::EnumMembersArray,
+)]
+pub enum KnownLayout {
+    Early,
+    Latest,
+}
+
 impl Version {
-    const EXPECTED: u16 = u16::from_le_bytes([0xA2, 0x05]);
+    const EXPECTED: u16 = KnownVersion::CURRENT.raw();
 
+    #[must_use]
     pub const fn current() -> Self {
         Self(Self::EXPECTED)
     }
 
+    #[must_use]
+    pub fn known(self) -> Option<KnownVersion> {
+        let Self(raw) = self;
+        KnownVersion::members()
+            .into_iter()
+            .find(|known| known.raw() == raw)
+    }
+
     fn parse_raw_num<R: binrw::io::Read + binrw::io::Seek>(
         reader: &mut R,
         endian: binrw::Endian,
@@ -28,18 +83,103 @@ impl Version {
     ) -> BinResult<u16> {
         let pos = reader.stream_position().unwrap_or_default();
         let actual_version = <u16 as BinRead>::read_options(reader, endian, args)?;
-        if Self::EXPECTED == actual_version {
-            Ok(Self::EXPECTED)
-        } else {
-            Err(binrw::Error::Custom {
+        match Self(actual_version).known() {
+            Some(known) => Ok(known.raw()),
+            None => Err(binrw::Error::Custom {
                 pos,
                 err: Box::new(UnsupportedVersion {
                     expected: Self::EXPECTED,
                     actual: actual_version,
                 }),
-            })
+            }),
+        }
+    }
+}
+
+impl Default for Version {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+impl KnownVersion {
+    pub const CURRENT: Self = Self::V1_9_2;
+
+    #[must_use]
+    pub const fn raw(self) -> u16 {
+        match self {
+            KnownVersion::V1_9_1 => u16::from_le_bytes([0xA1, 0x05]),
+            KnownVersion::V1_9_2 => u16::from_le_bytes([0xA2, 0x05]),
         }
     }
+
+    #[must_use]
+    pub const fn game_version(self) -> &'static str {
+        match self {
+            KnownVersion::V1_9_1 => "1.9.1",
+            KnownVersion::V1_9_2 => "1.9.2",
+        }
+    }
+}
+
+impl KnownLayout {
+    pub const CURRENT: Self = Self::Latest;
+
+    #[must_use]
+    pub const fn sizes(self) -> Layout {
+        match self {
+            KnownLayout::Early => Layout {
+                avatars: 0x1f,
+                backgrounds: 0x13,
+                titles: 0x01_11,
+            },
+            KnownLayout::Latest => Layout {
+                avatars: 0x21,
+                backgrounds: 0x13,
+                titles: 0x01_1d,
+            },
+        }
+    }
+
+    /// The known layout with exactly these section lengths, if any.
+    #[must_use]
+    pub fn detect(layout: Layout) -> Option<Self> {
+        Self::members()
+            .into_iter()
+            .find(|known| known.sizes() == layout)
+    }
+
+    /// Avatars that exist in this layout.
+    #[must_use]
+    pub fn avatars(self) -> Vec<avatar::Character> {
+        fitting(avatar::Character::members(), self.sizes().avatars)
+    }
+
+    /// Avatar backgrounds that exist in this layout.
+    #[must_use]
+    pub fn backgrounds(self) -> Vec<avatar::Background> {
+        fitting(avatar::Background::members(), self.sizes().backgrounds)
+    }
+
+    /// Titles that exist in this layout.
+    #[must_use]
+    pub fn titles(self) -> Vec<title::Text> {
+        fitting(title::Text::members(), self.sizes().titles)
+    }
+}
+
+/// Items whose id is an index in a section of `length` items.
+fn fitting<T: Copy + Into<u32>>(members: impl IntoIterator<Item = T>, length: usize) -> Vec<T> {
+    members
+        .into_iter()
+        .filter(|&item| usize::try_from(item.into()).is_ok_and(|id| id < length))
+        .collect()
+}
+
+impl From<KnownVersion> for Version {
+    fn from(known: KnownVersion) -> Self {
+        Self(known.raw())
+    }
 }
 
 impl Display for Version {
@@ -50,13 +190,30 @@ impl Display for Version {
     }
 }
 
+impl Display for KnownLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KnownLayout::Early => write!(f, "early layout"),
+            KnownLayout::Latest => write!(f, "latest layout"),
+        }
+    }
+}
+
+impl Display for KnownVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.game_version())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
 
     use binrw::{BinRead, BinWrite};
 
-    use super::Version;
+    use crate::{avatar, title};
+
+    use super::{KnownLayout, KnownVersion, Layout, Version};
 
     #[rstest::rstest]
     fn version_encodes_properly() {
@@ -81,6 +238,45 @@ mod tests {
         assert_eq!(expected_version, actual_version);
     }
 
+    #[rstest::rstest]
+    fn older_known_version_is_accepted() {
+        let mut reader = Cursor::new(b"\xA1\x05".to_vec());
+        let version = Version::read(&mut reader).expect("Must read here");
+
+        assert_eq!(Some(KnownVersion::V1_9_1), version.known());
+    }
+
+    #[rstest::rstest]
+    #[case(KnownLayout::Early)]
+    #[case(KnownLayout::Latest)]
+    fn layout_is_detected_from_its_sizes(#[case] known: KnownLayout) {
+        assert_eq!(Some(known), KnownLayout::detect(known.sizes()));
+    }
+
+    #[rstest::rstest]
+    fn unknown_layout_is_not_detected() {
+        let layout = Layout {
+            avatars: 0x20,
+            ..KnownLayout::CURRENT.sizes()
+        };
+
+        assert_eq!(None, KnownLayout::detect(layout));
+    }
+
+    #[rstest::rstest]
+    fn early_layout_lacks_later_items() {
+        let early = KnownLayout::Early;
+        let latest = KnownLayout::Latest;
+
+        assert!(early.avatars().contains(&avatar::Character::OjSuguriWinter));
+        assert!(!early.avatars().contains(&avatar::Character::OjIru));
+        assert!(latest.avatars().contains(&avatar::Character::OjMira));
+        assert!(!latest.avatars().contains(&avatar::Character::OjTsih));
+        assert_eq!(early.backgrounds(), latest.backgrounds());
+        assert!(early.titles().len() < latest.titles().len());
+        assert!(!latest.titles().contains(&title::Text::Disabled));
+    }
+
     #[rstest::rstest]
     fn bad_version_returns_proper_error() {
         let expected_err = binary_file::UnsupportedVersion {
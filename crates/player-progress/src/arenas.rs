@@ -10,6 +10,14 @@ use crate::{UnknownU8, lock::Status};
 #[brw(little)]
 #[br(map = From::<RawArenas>::from)]
 #[bw(map = RawArenas::from)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "crate::schema::ArenasSchema",
+        into = "crate::schema::ArenasSchema"
+    )
+)]
 pub struct Arenas {
     #[deref]
     #[as_ref(forward)]
@@ -57,6 +65,11 @@ struct RawArenas {
 )]
 #[try_from(repr)]
 #[repr(usize)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum Arena {
     #[display("Before the War")]
     BeforeTheWar = 0,
@@ -101,6 +114,17 @@ impl Arenas {
     pub fn toggle(&mut self, arena: Arena) {
         self[arena] = !self[arena];
     }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn unused_0x2d(&self) -> UnknownU8 {
+        self.unused_0x2d.clone()
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn with_unused_0x2d(mut self, unused_0x2d: UnknownU8) -> Self {
+        self.unused_0x2d = unused_0x2d;
+        self
+    }
 }
 
 impl Index<Arena> for Arenas {
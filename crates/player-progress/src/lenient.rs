@@ -0,0 +1,203 @@
+//! Reading damaged `game.sys` files, as much of them as possible.
+//!
+//! Every known field that doesn't make sense gets the value of a fresh file,
+//! and every replacement is reported, so nothing changes silently.
+
+use std::{io::Cursor, path::Path};
+
+use binary_file::Anomaly;
+use binrw::{BinRead, BinWrite};
+
+use crate::{
+    PlayerProgress,
+    raw::{self, FILE_SIZE, Field},
+    version::Version,
+};
+
+/// What's left of a damaged file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Salvaged {
+    pub progress: PlayerProgress,
+    pub anomalies: Vec<Anomaly>,
+}
+
+pub fn from_file(path: impl AsRef<Path>) -> Result<Salvaged, binary_file::Error> {
+    let path = path.as_ref();
+    let encrypted =
+        std::fs::read(path).map_err(|error| binary_file::Error::reading_file(path, error))?;
+    from_bytes(&encrypted).map_err(|error| binary_file::Error::reading_binary(path, error))
+}
+
+/// Fails only when there's nothing to replace, e.g. the version is too old.
+///
+/// Unknown versions are replaced with the current one, unlike with a strict read.
+pub fn from_bytes(encrypted: &[u8]) -> Result<Salvaged, binrw::Error> {
+    let fresh = fresh_bytes();
+    let mut decrypted = raw::decrypt_file(encrypted);
+    let mut anomalies = Vec::new();
+
+    if decrypted.len() != FILE_SIZE {
+        anomalies.push(Anomaly {
+            offset: decrypted.len().min(FILE_SIZE) as u64,
+            field: "file size".to_owned(),
+            expected: format!("{FILE_SIZE} bytes"),
+            actual: format!("{} bytes", decrypted.len()),
+        });
+        decrypted.truncate(FILE_SIZE);
+        decrypted.extend_from_slice(&fresh[decrypted.len()..]);
+    }
+
+    // Unknown versions are damage, while known older ones are files with another layout.
+    let version = raw::layout()
+        .into_iter()
+        .find(|field| field.name == "_version");
+    if let Some(field) = version {
+        let bytes = decrypted[field.offsets.clone()]
+            .try_into()
+            .unwrap_or_default();
+        if Version::is_unknown(u32::from_le_bytes(bytes)) {
+            replace(&mut decrypted, &fresh, field, &mut anomalies);
+        }
+    }
+
+    // Each round replaces a field that wasn't fresh, so it can't go on forever.
+    loop {
+        let error = match PlayerProgress::read(&mut Cursor::new(&decrypted)) {
+            Ok(progress) => {
+                return Ok(Salvaged {
+                    progress,
+                    anomalies,
+                });
+            }
+            Err(error) => error,
+        };
+
+        let Some(offset) = binary_file::format_error_position(&error)
+            .and_then(|offset| usize::try_from(offset).ok())
+            .filter(|&offset| offset < FILE_SIZE)
+        else {
+            return Err(error);
+        };
+        let Field { name, offsets } = raw::field_at(offset).unwrap_or_else(|| Field {
+            name: format!("_0x{offset:02x}"),
+            offsets: offset..offset + 1,
+        });
+        if decrypted[offsets.clone()] == fresh[offsets.clone()] {
+            return Err(error);
+        }
+        replace(
+            &mut decrypted,
+            &fresh,
+            Field { name, offsets },
+            &mut anomalies,
+        );
+    }
+}
+
+/// Puts fresh bytes in place of the field and reports it.
+fn replace(decrypted: &mut [u8], fresh: &[u8], field: Field, anomalies: &mut Vec<Anomaly>) {
+    let Field { name, offsets } = field;
+    anomalies.push(Anomaly {
+        offset: offsets.start as u64,
+        field: name,
+        expected: Anomaly::hex(&fresh[offsets.clone()]),
+        actual: Anomaly::hex(&decrypted[offsets.clone()]),
+    });
+    decrypted[offsets.clone()].copy_from_slice(&fresh[offsets]);
+}
+
+fn fresh_bytes() -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::with_capacity(FILE_SIZE));
+    PlayerProgress::default()
+        .write(&mut writer)
+        .expect("Invariant: Fresh file must be writable");
+    writer.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use binary_file::SaveFormat;
+
+    use crate::{Character, FileVersion, GameVersion, PlayerProgress, raw};
+
+    use super::from_bytes;
+
+    const CARGO_TOML: &str = env!("CARGO_MANIFEST_DIR");
+
+    #[rstest::fixture]
+    fn completionist_decrypted() -> Vec<u8> {
+        let path = PathBuf::from(CARGO_TOML).join("test_inputs/game-completionist.sys");
+        let encrypted = std::fs::read(path).expect("Precondition: must read test input file");
+        raw::decrypt(&encrypted).expect("Precondition: must decrypt")
+    }
+
+    #[rstest::rstest]
+    fn broken_fields_are_replaced_and_reported(mut completionist_decrypted: Vec<u8>) {
+        completionist_decrypted[0x04] = 0x00;
+        completionist_decrypted[0x0f] = 0x07;
+        let encrypted = raw::encrypt(&completionist_decrypted).expect("Precondition: must encrypt");
+
+        let salvaged = from_bytes(&encrypted).expect("Must salvage");
+
+        let fields: Vec<&str> = salvaged
+            .anomalies
+            .iter()
+            .map(|anomaly| anomaly.field.as_str())
+            .collect();
+        assert_eq!(vec!["_body_length", "playable_characters.sora"], fields);
+        assert_eq!("07", salvaged.anomalies[1].actual);
+        assert_eq!(
+            PlayerProgress::default().playable_characters[Character::Sora],
+            salvaged.progress.playable_characters[Character::Sora]
+        );
+        assert!(
+            salvaged.progress.playable_characters[Character::Hime].is_enabled(),
+            "Undamaged fields must stay"
+        );
+    }
+
+    #[rstest::rstest]
+    fn intact_file_has_no_anomalies(completionist_decrypted: Vec<u8>) {
+        let encrypted = raw::encrypt(&completionist_decrypted).expect("Precondition: must encrypt");
+
+        let salvaged = from_bytes(&encrypted).expect("Must read");
+
+        assert!(salvaged.anomalies.is_empty());
+    }
+
+    #[rstest::rstest]
+    fn unknown_version_is_replaced(mut completionist_decrypted: Vec<u8>) {
+        completionist_decrypted[0x08..0x0c].copy_from_slice(&[0x42, 0x05, 0x00, 0x00]);
+        let encrypted = raw::encrypt(&completionist_decrypted).expect("Precondition: must encrypt");
+        PlayerProgress::from_bytes(&encrypted).expect_err("Precondition: strict read must fail");
+
+        let salvaged = from_bytes(&encrypted).expect("Must salvage");
+
+        assert_eq!(1, salvaged.anomalies.len());
+        assert_eq!("_version", salvaged.anomalies[0].field);
+        assert_eq!("42 05 00 00", salvaged.anomalies[0].actual);
+        assert_eq!(
+            FileVersion::Known(GameVersion::CURRENT),
+            salvaged.progress.version()
+        );
+    }
+
+    #[rstest::rstest]
+    fn older_version_is_not_replaced(mut completionist_decrypted: Vec<u8>) {
+        completionist_decrypted[0x08..0x0c]
+            .copy_from_slice(&GameVersion::V1_8_4.raw().to_le_bytes());
+        let encrypted = raw::encrypt(&completionist_decrypted).expect("Precondition: must encrypt");
+
+        from_bytes(&encrypted).expect_err("Must fail");
+    }
+
+    #[rstest::rstest]
+    fn truncated_file_is_padded_with_fresh_bytes() {
+        let salvaged = from_bytes(&[]).expect("Must salvage");
+
+        assert_eq!(PlayerProgress::default(), salvaged.progress);
+        assert_eq!("file size", salvaged.anomalies[0].field);
+    }
+}
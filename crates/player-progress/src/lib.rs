@@ -2,14 +2,20 @@
 #![allow(clippy::missing_errors_doc)]
 
 // No export
+#[cfg(feature = "serde")]
+mod schema;
 mod xor_encryption;
 
+pub mod lenient;
+pub mod raw;
+
 // Re-export.
 mod arenas;
 mod lock;
 mod music;
 mod playable_characters;
 mod runs;
+mod version;
 mod wins;
 
 pub use self::arenas::{Arena, Arenas};
@@ -17,31 +23,34 @@ pub use self::lock::Status;
 pub use self::music::{MusicTrack, MusicTracks};
 pub use self::playable_characters::{Character, PlayableCharacters};
 pub use self::runs::{PerfectArcadeMode, PerfectStoryMode, Run};
-pub use self::wins::SingleplayerWins;
+pub use self::version::{FileVersion, GameVersion};
+pub use self::wins::{CounterMismatch, OneCreditCounter, SingleplayerWins};
 
-use std::{io::Cursor, path::Path};
+use std::io::{Cursor, Read, Seek, Write};
 
-use aos2_env::AoS2Env;
-use binary_file::UnknownU8;
-use binrw::{BinRead, BinWrite};
+use binary_file::{SaveFormat, UnknownU8};
+use binrw::{BinRead, BinResult, BinWrite};
 
-use crate::xor_encryption::{EncryptedU8, KeyU8};
+use crate::version::Version;
 
 /// Player progress file, aka `game.sys`.
 ///
 /// Order of fields MATTERS. Do NOT reorder.
 #[binrw::binrw]
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[brw(little)]
 pub struct PlayerProgress {
-    _0x00: UnknownU8,
-    _0x01: UnknownU8,
-    _0x02: UnknownU8,
+    /// Spells `LSD`, maybe a signature.
+    _0x00: FreshU8<b'L'>,
+    _0x01: FreshU8<b'S'>,
+    _0x02: FreshU8<b'D'>,
     _0x03: UnknownU8,
     /// Offset: 0x04 - 0x07.
     _body_length: BodyLength,
     /// Offset: 0x08 - 0x0b
-    _version: Version,
+    #[cfg_attr(feature = "serde", serde(rename = "_version"))]
+    version: Version,
     _0x0c: UnknownU8,
     _0x0d: UnknownU8,
     _0x0e: UnknownU8,
@@ -124,9 +133,31 @@ pub struct PlayerProgress {
     _0xab: UnknownU8,
 }
 
+/// Unknown byte that's always the same in fresh files.
+#[binrw::binrw]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[brw(little)]
+struct FreshU8<const FRESH: u8>(u8);
+
+impl<const FRESH: u8> Default for FreshU8<FRESH> {
+    fn default() -> Self {
+        Self(FRESH)
+    }
+}
+
 /// Somehow it just matches.
 #[binrw::binrw]
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[brw(little)]
 #[br(assert(self_0 == Self::BYTES))]
 struct BodyLength(u32);
@@ -135,27 +166,11 @@ impl BodyLength {
     const BYTES: u32 = 164;
 }
 
-/// Reprents savefile version, parsed by a specific game version.
-///
-/// Here's the table with the values of
-/// the least signigicant byte for each game version.
-///
-/// | Game version | Byte value | Hex value |
-/// |:------------:|:----------:|:---------:|
-/// |  1.6 (demo)  |     135    |   0x87    |
-/// |     1.6.2    |     137    |   0x89    |
-/// |    1.6.3b    |     138    |   0x8A    |
-/// |     1.7.6    |     152    |   0x98    |
-/// |     1.8.4    |     160    |   0xA0    |
-/// |      1.9     |     161    |   0xA1    |
-///
-/// Therefore, version is the most probable explanation,
-/// because in a fresh savefile all other values are the same,
-/// except for the one at `0x08`.
-#[binrw::binrw]
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
-#[brw(little)]
-struct Version(u32);
+impl Default for BodyLength {
+    fn default() -> Self {
+        Self(Self::BYTES)
+    }
+}
 
 #[binrw::binrw]
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -172,53 +187,31 @@ struct EncryptedProgress {
 struct EncryptionError(#[from] binrw::Error);
 
 impl PlayerProgress {
-    pub const FILE_NAME: &'static str = "game.sys";
-
-    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, binary_file::Error> {
-        EncryptedProgress::from_file(path.as_ref())?
-            .try_into()
-            .map_err(|EncryptionError(err)| binary_file::Error::reading_binary(path.as_ref(), err))
-    }
-
-    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), binary_file::Error> {
-        EncryptedProgress::try_from(self.clone())
-            .map_err(|EncryptionError(err)| binary_file::Error::writing_binary(path.as_ref(), err))?
-            .save_to_file(path.as_ref())
-    }
-
-    pub fn save(&self, env: &AoS2Env) -> Result<(), binary_file::Error> {
-        self.save_to_file(env.saves_folder.join(Self::FILE_NAME))
-    }
-
-    pub fn load(env: &AoS2Env) -> Result<Self, binary_file::Error> {
-        Self::from_file(env.saves_folder.join(Self::FILE_NAME))
+    /// Game version that wrote the file, if the file says.
+    #[must_use]
+    pub fn version(&self) -> FileVersion {
+        self.version.file_version()
     }
 }
 
 impl EncryptedProgress {
-    pub const TOTAL_SIZE: usize = 172;
-    pub const HEADER_SIZE: usize = 8;
-    pub const BODY_SIZE: usize = Self::TOTAL_SIZE - Self::HEADER_SIZE;
-    pub const ENCRYPTION_START_KEY: KeyU8 = KeyU8::new(0x4A);
+    pub const TOTAL_SIZE: usize = raw::FILE_SIZE;
+    pub const HEADER_SIZE: usize = raw::HEADER_SIZE;
+    pub const BODY_SIZE: usize = raw::BODY_SIZE;
+}
 
-    pub fn from_file(path: &Path) -> Result<Self, binary_file::Error> {
-        let mut reader =
-            std::fs::File::open(path).map_err(|err| binary_file::Error::reading_file(path, err))?;
+impl SaveFormat for PlayerProgress {
+    const FILE_NAME: &'static str = "game.sys";
 
-        <Self as BinRead>::read(&mut reader)
-            .map_err(|err| binary_file::Error::reading_binary(path, err))
+    fn read_from<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
+        let encrypted = <EncryptedProgress as BinRead>::read(reader)?;
+        encrypted.try_into().map_err(|EncryptionError(err)| err)
     }
 
-    pub fn save_to_file(&self, path: &Path) -> Result<(), binary_file::Error> {
-        let mut writer = std::fs::File::options()
-            .create(false)
-            .write(true)
-            .truncate(true)
-            .open(path)
-            .map_err(|err| binary_file::Error::writing_file(path, err))?;
-
-        BinWrite::write(self, &mut writer)
-            .map_err(|err| binary_file::Error::writing_binary(path, err))
+    fn write_to<W: Write + Seek>(&self, writer: &mut W) -> BinResult<()> {
+        EncryptedProgress::try_from(self.clone())
+            .map_err(|EncryptionError(err)| err)?
+            .write(writer)
     }
 }
 
@@ -228,11 +221,8 @@ impl TryFrom<EncryptedProgress> for PlayerProgress {
     fn try_from(
         EncryptedProgress { header, body }: EncryptedProgress,
     ) -> Result<Self, Self::Error> {
-        let decrypted_body = body.into_iter().enumerate().map(|(index, byte)| {
-            let key = EncryptedProgress::ENCRYPTION_START_KEY.wrapping_add_usize(index);
-            EncryptedU8::encrypted(byte).decrypt(key)
-        });
-        let raw_decrypted: Vec<u8> = header.into_iter().chain(decrypted_body).collect();
+        let encrypted: Vec<u8> = header.into_iter().chain(body).collect();
+        let raw_decrypted = raw::decrypt_file(&encrypted);
 
         let mut reader = Cursor::new(raw_decrypted);
         BinRead::read(&mut reader).map_err(EncryptionError)
@@ -254,14 +244,7 @@ impl TryFrom<PlayerProgress> for EncryptedProgress {
                 .expect("Invariant: Arrays of fixed and known size must match")
                 .to_owned();
 
-        let body: [u8; Self::BODY_SIZE] = buffer[Self::HEADER_SIZE..]
-            .iter()
-            .enumerate()
-            .map(|(index, &byte)| {
-                let key = Self::ENCRYPTION_START_KEY.wrapping_add_usize(index);
-                EncryptedU8::encrypt(byte, key).get()
-            })
-            .collect::<Vec<u8>>()
+        let body: [u8; Self::BODY_SIZE] = raw::encrypt_file(&buffer)[Self::HEADER_SIZE..]
             .try_into()
             .expect("Invariant: Arrays of fixed and known size must match");
 
@@ -273,6 +256,7 @@ impl TryFrom<PlayerProgress> for EncryptedProgress {
 mod tests {
     use std::{io::Cursor, path::PathBuf};
 
+    use binary_file::SaveFormat;
     use binrw::{BinRead, BinWrite};
 
     use super::{EncryptedProgress, PlayerProgress};
@@ -311,4 +295,55 @@ mod tests {
 
         assert_eq!(expected_savefile, writer.into_inner());
     }
+
+    #[rstest::rstest]
+    #[case::fresh(fresh_savefile())]
+    #[case::lots_of_stuff_unlocked(completionist_savefile())]
+    fn progress_file_bytes_roundtrip(#[case] expected_savefile: Vec<u8>) {
+        let player_progress =
+            PlayerProgress::from_bytes(&expected_savefile).expect("Must read the file");
+
+        let bytes = player_progress.to_bytes().expect("Must write the file");
+
+        assert_eq!(expected_savefile, bytes);
+    }
+
+    #[rstest::rstest]
+    fn default_is_a_fresh_file(fresh_savefile: Vec<u8>) {
+        let encrypted_progress: EncryptedProgress = PlayerProgress::default()
+            .try_into()
+            .expect("Must encrypt successfully");
+        let mut writer = Cursor::new(Vec::new());
+        BinWrite::write(&encrypted_progress, &mut writer).expect("Must write to a buffer");
+
+        assert_eq!(fresh_savefile, writer.into_inner());
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest::rstest]
+    #[case::fresh(fresh_savefile())]
+    #[case::lots_of_stuff_unlocked(completionist_savefile())]
+    fn progress_file_serde_roundtrip(#[case] expected_savefile: Vec<u8>) {
+        let player_progress = {
+            let mut reader = Cursor::new(expected_savefile.clone());
+            let encrypted: EncryptedProgress =
+                BinRead::read(&mut reader).expect("Must parse the binary file");
+
+            PlayerProgress::try_from(encrypted).expect("Must decrypt the encrypted file")
+        };
+
+        let json = serde_json::to_string(&player_progress).expect("Must serialize to JSON");
+        let from_json: PlayerProgress = serde_json::from_str(&json).expect("Must read JSON back");
+        let toml = toml::to_string(&player_progress).expect("Must serialize to TOML");
+        let from_toml: PlayerProgress = toml::from_str(&toml).expect("Must read TOML back");
+
+        for deserialized in [from_json, from_toml] {
+            let encrypted_progress: EncryptedProgress =
+                deserialized.try_into().expect("Must encrypt successfully");
+            let mut writer = Cursor::new(Vec::new());
+            BinWrite::write(&encrypted_progress, &mut writer).expect("Must write to a buffer");
+
+            assert_eq!(expected_savefile, writer.into_inner());
+        }
+    }
 }
@@ -1,5 +1,10 @@
 #[binrw::binrw]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 #[brw(little)]
 pub enum Status {
     #[brw(magic = 0x01u8)]
@@ -10,6 +10,14 @@ use crate::lock::Status;
 #[derive(Debug, Clone, PartialEq, Eq, Hash, derive_more::Deref, derive_more::AsRef)]
 #[brw(little)]
 #[as_ref(forward)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "crate::schema::StatusMap<MusicTrack>",
+        into = "crate::schema::StatusMap<MusicTrack>"
+    )
+)]
 pub struct MusicTracks([Status; MusicTracks::AMOUNT]);
 
 #[derive(
@@ -27,6 +35,11 @@ pub struct MusicTracks([Status; MusicTracks::AMOUNT]);
 )]
 #[try_from(repr)]
 #[repr(usize)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum MusicTrack {
     #[display("Need for Speed")]
     NeedForSpeed = 0,
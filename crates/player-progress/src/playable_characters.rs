@@ -6,6 +6,14 @@ use crate::Status;
 #[derive(Debug, Clone, PartialEq, Eq, Hash, derive_more::Deref, derive_more::AsRef)]
 #[brw(little)]
 #[as_ref(forward)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "crate::schema::StatusMap<Character>",
+        into = "crate::schema::StatusMap<Character>"
+    )
+)]
 pub struct PlayableCharacters([Status; PlayableCharacters::AMOUNT]);
 
 /// IMPORTANT: ORDER AND INDEX VALUES MATTER.
@@ -25,6 +33,11 @@ pub struct PlayableCharacters([Status; PlayableCharacters::AMOUNT]);
 )]
 #[try_from(repr)]
 #[repr(usize)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum Character {
     Sora = 0,
     Alte = 1,
@@ -0,0 +1,304 @@
+//! Raw bytes of `game.sys`, for those who want to look under the hood.
+//!
+//! Only the body is encrypted, the header stays as is.
+
+use std::ops::Range;
+
+use crate::{
+    Arena, Character, MusicTrack, PerfectArcadeMode, PerfectStoryMode,
+    xor_encryption::{EncryptedU8, KeyU8},
+};
+
+pub const FILE_SIZE: usize = 172;
+pub const HEADER_SIZE: usize = 8;
+pub const BODY_SIZE: usize = FILE_SIZE - HEADER_SIZE;
+
+const ENCRYPTION_START_KEY: KeyU8 = KeyU8::new(0x4A);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("`game.sys` must be exactly {FILE_SIZE} bytes, but got {0}")]
+pub struct WrongSize(pub usize);
+
+/// A known field in the decrypted file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    pub offsets: Range<usize>,
+}
+
+/// Decrypts a whole `game.sys` file.
+pub fn decrypt(encrypted: &[u8]) -> Result<Vec<u8>, WrongSize> {
+    check_size(encrypted)?;
+    Ok(decrypt_file(encrypted))
+}
+
+/// Encrypts a whole `game.sys` file back.
+pub fn encrypt(decrypted: &[u8]) -> Result<Vec<u8>, WrongSize> {
+    check_size(decrypted)?;
+    Ok(encrypt_file(decrypted))
+}
+
+/// All known fields of the decrypted file, in order.
+///
+/// Bytes that don't belong to any field are unknown.
+/// Lists are split into one field per item, e.g. `playable_characters.sora`.
+#[must_use]
+pub fn layout() -> Vec<Field> {
+    let characters: Vec<String> = Character::members().iter().map(snake_case).collect();
+    let arenas: Vec<String> = Arena::members().iter().map(snake_case).collect();
+    let music: Vec<String> = MusicTrack::members().iter().map(snake_case).collect();
+    let arcade = &characters[..PerfectArcadeMode::N_CHARACTERS];
+    let story = &characters[..PerfectStoryMode::N_CHARACTERS];
+    // The game keeps an unused byte right before this arena.
+    let (arenas_before, arenas_after) = arenas.split_at(Arena::Nature10k as usize);
+
+    let mut layout = Layout::default();
+    layout.unknown(4);
+    layout.field("_body_length", 4);
+    layout.field("_version", 4);
+    layout.unknown(3);
+    layout.items("playable_characters", &characters);
+    layout.unknown(6);
+    layout.items("arenas.unlocked", arenas_before);
+    layout.field("arenas.unused_0x2d", 1);
+    layout.items("arenas.unlocked", arenas_after);
+    layout.unknown(11);
+    layout.items("music_tracks", &music);
+    layout.unknown(3);
+    for wins in [
+        "total",
+        "n_arcade_easy_1ccs",
+        "n_arcade_medium_1ccs",
+        "n_arcade_hard_1ccs",
+        "n_story_1ccs",
+    ] {
+        layout.field(&format!("wins.{wins}"), 4);
+    }
+    layout.unknown(3);
+    layout.items("arcade_easy_1ccs", arcade);
+    layout.unknown(4);
+    layout.items("arcade_medium_1ccs", arcade);
+    layout.unknown(4);
+    layout.items("arcade_hard_1ccs", arcade);
+    layout.unknown(4);
+    layout.items("story_1ccs", story);
+    layout.unknown(2);
+
+    layout.fields
+}
+
+/// The known field that covers the byte, if any.
+#[must_use]
+pub fn field_at(offset: usize) -> Option<Field> {
+    layout()
+        .into_iter()
+        .find(|field| field.offsets.contains(&offset))
+}
+
+pub(crate) fn decrypt_file(encrypted: &[u8]) -> Vec<u8> {
+    transform_body(encrypted, |byte, key| {
+        EncryptedU8::encrypted(byte).decrypt(key)
+    })
+}
+
+pub(crate) fn encrypt_file(decrypted: &[u8]) -> Vec<u8> {
+    transform_body(decrypted, |byte, key| EncryptedU8::encrypt(byte, key).get())
+}
+
+fn transform_body(file: &[u8], transform: impl Fn(u8, KeyU8) -> u8) -> Vec<u8> {
+    let (header, body) = file.split_at(HEADER_SIZE.min(file.len()));
+    let body = body.iter().enumerate().map(|(index, &byte)| {
+        let key = ENCRYPTION_START_KEY.wrapping_add_usize(index);
+        transform(byte, key)
+    });
+    header.iter().copied().chain(body).collect()
+}
+
+fn check_size(file: &[u8]) -> Result<(), WrongSize> {
+    if file.len() == FILE_SIZE {
+        Ok(())
+    } else {
+        Err(WrongSize(file.len()))
+    }
+}
+
+/// Same names as in the exported files, so `StarBreaker` is `star_breaker`.
+fn snake_case(member: &impl std::fmt::Debug) -> String {
+    let mut name = String::new();
+    for char in format!("{member:?}").chars() {
+        if char.is_ascii_uppercase() && !name.is_empty() {
+            name.push('_');
+        }
+        name.push(char.to_ascii_lowercase());
+    }
+    name
+}
+
+#[derive(Debug, Default)]
+struct Layout {
+    offset: usize,
+    fields: Vec<Field>,
+}
+
+impl Layout {
+    fn unknown(&mut self, size: usize) {
+        self.offset += size;
+    }
+
+    fn field(&mut self, name: &str, size: usize) {
+        self.fields.push(Field {
+            name: name.to_owned(),
+            offsets: self.offset..self.offset + size,
+        });
+        self.offset += size;
+    }
+
+    fn items(&mut self, prefix: &str, names: &[String]) {
+        for name in names {
+            self.field(&format!("{prefix}.{name}"), 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{FILE_SIZE, decrypt, encrypt, field_at, layout};
+    #[cfg(feature = "serde")]
+    use crate::PlayerProgress;
+
+    const CARGO_TOML: &str = env!("CARGO_MANIFEST_DIR");
+
+    #[rstest::rstest]
+    #[case("game-fresh.sys")]
+    #[case("game-completionist.sys")]
+    fn decrypt_encrypt_roundtrip(#[case] file_name: &str) {
+        let path = PathBuf::from(CARGO_TOML)
+            .join("test_inputs")
+            .join(file_name);
+        let encrypted = std::fs::read(path).expect("Precondition: must read test input file");
+
+        let decrypted = decrypt(&encrypted).expect("Must decrypt");
+
+        assert_eq!(b"LSD\0", &decrypted[..4]);
+        assert_eq!(encrypted, encrypt(&decrypted).expect("Must encrypt"));
+    }
+
+    #[rstest::rstest]
+    fn wrong_size_is_refused() {
+        decrypt(&[0; FILE_SIZE - 1]).expect_err("Must fail");
+    }
+
+    #[rstest::rstest]
+    fn layout_fits_the_file() {
+        let fields = layout();
+
+        for pair in fields.windows(2) {
+            assert!(pair[0].offsets.end <= pair[1].offsets.start);
+        }
+        assert_eq!(FILE_SIZE - 2, fields.last().expect("Not empty").offsets.end);
+    }
+
+    #[rstest::rstest]
+    #[case(0x0f, Some("playable_characters.sora"))]
+    #[case(0x15, Some("playable_characters.star_breaker"))]
+    #[case(0x2d, Some("arenas.unused_0x2d"))]
+    #[case(0x32, Some("arenas.unlocked.sumika_hideout"))]
+    #[case(0x4d, Some("wins.total"))]
+    #[case(0x9c, Some("story_1ccs.sora"))]
+    #[case(0x1e, None)]
+    fn fields_are_at_documented_offsets(#[case] offset: usize, #[case] expected: Option<&str>) {
+        let actual = field_at(offset).map(|field| field.name);
+
+        assert_eq!(expected.map(ToOwned::to_owned), actual);
+    }
+
+    /// Field names with their values, as in the exported files.
+    #[cfg(feature = "serde")]
+    fn exported_fields(progress: &PlayerProgress) -> Vec<(String, serde_json::Value)> {
+        fn flatten(
+            prefix: &str,
+            value: serde_json::Value,
+            fields: &mut Vec<(String, serde_json::Value)>,
+        ) {
+            match value {
+                serde_json::Value::Object(map) => {
+                    for (key, value) in map {
+                        let name = if prefix.is_empty() {
+                            key
+                        } else {
+                            format!("{prefix}.{key}")
+                        };
+                        flatten(&name, value, fields);
+                    }
+                }
+                leaf => fields.push((prefix.to_owned(), leaf)),
+            }
+        }
+
+        let mut fields = Vec::new();
+        flatten(
+            "",
+            serde_json::to_value(progress).expect("Must serialize"),
+            &mut fields,
+        );
+        fields
+    }
+
+    /// Every exported field that isn't an unknown byte is in the layout, exactly once.
+    #[cfg(feature = "serde")]
+    #[rstest::rstest]
+    fn layout_names_every_known_field_once() {
+        let mut expected: Vec<String> = exported_fields(&PlayerProgress::default())
+            .into_iter()
+            .map(|(name, _)| name)
+            .filter(|name| !name.starts_with("_0x"))
+            .collect();
+        let mut actual: Vec<String> = layout().into_iter().map(|field| field.name).collect();
+        expected.sort();
+        actual.sort();
+
+        assert_eq!(expected, actual);
+    }
+
+    /// Changing a byte changes only the field the layout puts there.
+    #[cfg(feature = "serde")]
+    #[rstest::rstest]
+    fn layout_offsets_match_the_struct() {
+        use binrw::{BinRead, BinWrite};
+
+        let fresh = {
+            let mut writer = std::io::Cursor::new(Vec::new());
+            PlayerProgress::default()
+                .write(&mut writer)
+                .expect("Must write");
+            writer.into_inner()
+        };
+        let fresh_fields = exported_fields(&PlayerProgress::default());
+
+        for offset in 0..FILE_SIZE {
+            let expected =
+                field_at(offset).map_or_else(|| format!("_0x{offset:02x}"), |field| field.name);
+            for value in [0x00, 0x01, 0x02, 0x03, 0xff] {
+                let mut bytes = fresh.clone();
+                bytes[offset] = value;
+                // Some bytes only allow a few values.
+                let Ok(progress) = PlayerProgress::read(&mut std::io::Cursor::new(bytes)) else {
+                    continue;
+                };
+
+                let changed: Vec<String> = exported_fields(&progress)
+                    .into_iter()
+                    .zip(&fresh_fields)
+                    .filter(|((_, value), (_, fresh))| value != fresh)
+                    .map(|((name, _), _)| name)
+                    .collect();
+                assert!(
+                    changed.is_empty() || changed == [expected.clone()],
+                    "Byte {offset:#04x} set to {value:#04x} changed {changed:?}, expected {expected}"
+                );
+            }
+        }
+    }
+}
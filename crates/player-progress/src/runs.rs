@@ -4,6 +4,7 @@
 /// because she doesn't have her own Story mode run.
 #[binrw::binrw]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[brw(little)]
 pub struct PerfectStoryMode {
     pub sora: Run,
@@ -27,6 +28,7 @@ pub struct PerfectStoryMode {
 /// This time Sumika is here, cos she has Arcade mode runs
 #[binrw::binrw]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[brw(little)]
 pub struct PerfectArcadeMode {
     pub sora: Run,
@@ -48,6 +50,11 @@ pub struct PerfectArcadeMode {
 
 #[binrw::binrw]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 #[brw(little)]
 pub enum Run {
     #[brw(magic = 0x01u8)]
@@ -67,6 +74,17 @@ impl Run {
     }
 }
 
+impl std::ops::Not for Run {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        match self {
+            Run::Completed => Run::NotCompleted,
+            Run::NotCompleted => Run::Completed,
+        }
+    }
+}
+
 impl PerfectStoryMode {
     pub const N_CHARACTERS: usize = 14;
 
@@ -91,6 +109,15 @@ impl PerfectStoryMode {
     pub fn to_array(&self) -> [Run; Self::N_CHARACTERS] {
         self.clone().into()
     }
+
+    /// How many characters have a star.
+    #[must_use]
+    pub fn n_completed(&self) -> usize {
+        self.to_array()
+            .into_iter()
+            .filter(|run| run.is_completed())
+            .count()
+    }
 }
 
 impl PerfectArcadeMode {
@@ -118,6 +145,15 @@ impl PerfectArcadeMode {
     pub fn to_array(&self) -> [Run; Self::N_CHARACTERS] {
         self.clone().into()
     }
+
+    /// How many characters have a star.
+    #[must_use]
+    pub fn n_completed(&self) -> usize {
+        self.to_array()
+            .into_iter()
+            .filter(|run| run.is_completed())
+            .count()
+    }
 }
 
 impl From<PerfectStoryMode> for [Run; PerfectStoryMode::N_CHARACTERS] {
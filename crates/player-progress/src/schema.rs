@@ -0,0 +1,105 @@
+//! Human-readable representation of the lists that are stored as bare arrays.
+//!
+//! Arrays are indexed by enums, so they become maps like `{ "sora": "enabled" }`
+//! instead of meaningless lists of statuses.
+
+use std::{collections::BTreeMap, fmt::Display, ops::IndexMut};
+
+use binary_file::UnknownU8;
+
+use crate::{Arena, Arenas, Character, MusicTrack, MusicTracks, PlayableCharacters, Status};
+
+pub type StatusMap<K> = BTreeMap<K, Status>;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ArenasSchema {
+    pub unlocked: StatusMap<Arena>,
+    pub unused_0x2d: UnknownU8,
+}
+
+/// Every member must be listed, otherwise the file can't be recreated as is.
+#[derive(Debug, thiserror::Error)]
+#[error("`{0}` is missing")]
+pub struct MissingMember(String);
+
+fn to_map<K, T>(container: &T, members: impl IntoIterator<Item = K>) -> StatusMap<K>
+where
+    K: Ord + Copy,
+    T: IndexMut<K, Output = Status>,
+{
+    members
+        .into_iter()
+        .map(|member| (member, container[member]))
+        .collect()
+}
+
+fn from_map<K, T>(
+    mut container: T,
+    members: impl IntoIterator<Item = K>,
+    map: &StatusMap<K>,
+) -> Result<T, MissingMember>
+where
+    K: Ord + Copy + Display,
+    T: IndexMut<K, Output = Status>,
+{
+    for member in members {
+        container[member] = *map
+            .get(&member)
+            .ok_or_else(|| MissingMember(member.to_string()))?;
+    }
+    Ok(container)
+}
+
+impl From<PlayableCharacters> for StatusMap<Character> {
+    fn from(characters: PlayableCharacters) -> Self {
+        to_map(&characters, Character::members())
+    }
+}
+
+impl TryFrom<StatusMap<Character>> for PlayableCharacters {
+    type Error = MissingMember;
+
+    fn try_from(map: StatusMap<Character>) -> Result<Self, Self::Error> {
+        from_map(Self::ALL, Character::members(), &map)
+    }
+}
+
+impl From<MusicTracks> for StatusMap<MusicTrack> {
+    fn from(music: MusicTracks) -> Self {
+        to_map(&music, MusicTrack::members())
+    }
+}
+
+impl TryFrom<StatusMap<MusicTrack>> for MusicTracks {
+    type Error = MissingMember;
+
+    fn try_from(map: StatusMap<MusicTrack>) -> Result<Self, Self::Error> {
+        from_map(Self::ALL, MusicTrack::members(), &map)
+    }
+}
+
+impl From<Arenas> for ArenasSchema {
+    fn from(arenas: Arenas) -> Self {
+        Self {
+            unlocked: to_map(&arenas, Arena::members()),
+            unused_0x2d: arenas.unused_0x2d(),
+        }
+    }
+}
+
+impl TryFrom<ArenasSchema> for Arenas {
+    type Error = MissingMember;
+
+    fn try_from(
+        ArenasSchema {
+            unlocked,
+            unused_0x2d,
+        }: ArenasSchema,
+    ) -> Result<Self, Self::Error> {
+        from_map(
+            Self::ALL.with_unused_0x2d(unused_0x2d),
+            Arena::members(),
+            &unlocked,
+        )
+    }
+}
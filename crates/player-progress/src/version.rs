@@ -0,0 +1,228 @@
+use std::fmt::Display;
+
+use binary_file::UnsupportedVersion;
+use binrw::{BinRead, BinResult};
+
+/// Reprents savefile version, parsed by a specific game version.
+///
+/// Here's the table with the values of
+/// the least signigicant byte for each game version.
+///
+/// | Game version | Byte value | Hex value |
+/// |:------------:|:----------:|:---------:|
+/// |  1.6 (demo)  |     135    |   0x87    |
+/// |     1.6.2    |     137    |   0x89    |
+/// |    1.6.3b    |     138    |   0x8A    |
+/// |     1.7.6    |     152    |   0x98    |
+/// |     1.8.4    |     160    |   0xA0    |
+/// |      1.9     |     161    |   0xA1    |
+///
+/// Therefore, version is the most probable explanation,
+/// because in a fresh savefile all other values are the same,
+/// except for the one at `0x08`.
+/// The next byte is always `0x05`, and the two after it are zeroes,
+/// so the whole field is checked, not just the byte from the table.
+///
+/// Files from older known versions are rejected, because their layout is different.
+/// So are unknown values, except for zeroes: `game-completionist.sys`,
+/// a real 1.9 file, has them.
+#[binrw::binrw]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u32", into = "u32")
+)]
+#[brw(little)]
+pub(crate) struct Version(#[br(parse_with = Version::parse_raw_num)] u32);
+
+/// Game version that wrote the file, see [`Version`] for the table.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, enum_array::EnumMembersArray,
+)]
+pub enum GameVersion {
+    Demo1_6,
+    V1_6_2,
+    V1_6_3b,
+    V1_7_6,
+    V1_8_4,
+    V1_9,
+}
+
+/// What the version field of a readable file says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileVersion {
+    Known(GameVersion),
+    /// Zeroes instead of a version, the layout is still the 1.9 one.
+    Zeroed,
+}
+
+impl Version {
+    const EXPECTED: u32 = GameVersion::CURRENT.raw();
+
+    const ZEROED: u32 = 0;
+
+    #[must_use]
+    pub(crate) fn file_version(self) -> FileVersion {
+        match self.game() {
+            Some(game) => FileVersion::Known(game),
+            None => FileVersion::Zeroed,
+        }
+    }
+
+    /// Neither a game version nor zeroes, which only a damaged file has.
+    #[must_use]
+    pub(crate) fn is_unknown(raw: u32) -> bool {
+        Self(raw).game().is_none() && raw != Self::ZEROED
+    }
+
+    #[must_use]
+    fn game(self) -> Option<GameVersion> {
+        let Self(raw) = self;
+        GameVersion::members()
+            .into_iter()
+            .find(|game| game.raw() == raw)
+    }
+
+    /// [`UnsupportedVersion`] is shared with `player.rkg`, which has a 2-byte version.
+    const fn lower_half(raw: u32) -> u16 {
+        let [xx00, xx01, _, _] = raw.to_le_bytes();
+        u16::from_le_bytes([xx00, xx01])
+    }
+
+    fn check(raw: u32) -> Result<Self, UnsupportedVersion> {
+        let version = Self(raw);
+        match version.game() {
+            Some(game) if game.is_supported() => Ok(version),
+            None if raw == Self::ZEROED => Ok(version),
+            _ => Err(UnsupportedVersion {
+                expected: Self::lower_half(Self::EXPECTED),
+                actual: Self::lower_half(raw),
+            }),
+        }
+    }
+
+    fn parse_raw_num<R: binrw::io::Read + binrw::io::Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        args: (),
+    ) -> BinResult<u32> {
+        let pos = reader.stream_position().unwrap_or_default();
+        let actual_version = <u32 as BinRead>::read_options(reader, endian, args)?;
+        Self::check(actual_version)
+            .map(|_| actual_version)
+            .map_err(|err| binrw::Error::Custom {
+                pos,
+                err: Box::new(err),
+            })
+    }
+}
+
+impl TryFrom<u32> for Version {
+    type Error = UnsupportedVersion;
+
+    fn try_from(raw: u32) -> Result<Self, Self::Error> {
+        Self::check(raw)
+    }
+}
+
+impl From<Version> for u32 {
+    fn from(version: Version) -> Self {
+        let Version(raw) = version;
+        raw
+    }
+}
+
+/// What a fresh file of the latest game version has.
+impl Default for Version {
+    fn default() -> Self {
+        Self(Self::EXPECTED)
+    }
+}
+
+impl GameVersion {
+    pub const CURRENT: Self = Self::V1_9;
+
+    /// The least significant byte of the version field.
+    #[must_use]
+    pub const fn byte(self) -> u8 {
+        match self {
+            GameVersion::Demo1_6 => 0x87,
+            GameVersion::V1_6_2 => 0x89,
+            GameVersion::V1_6_3b => 0x8A,
+            GameVersion::V1_7_6 => 0x98,
+            GameVersion::V1_8_4 => 0xA0,
+            GameVersion::V1_9 => 0xA1,
+        }
+    }
+
+    /// The whole version field, as the game writes it.
+    #[must_use]
+    pub const fn raw(self) -> u32 {
+        u32::from_le_bytes([self.byte(), 0x05, 0x00, 0x00])
+    }
+
+    /// Whether the editor understands files of this version.
+    ///
+    /// Older versions had fewer characters, arenas and music tracks.
+    #[must_use]
+    pub const fn is_supported(self) -> bool {
+        matches!(self, GameVersion::V1_9)
+    }
+}
+
+impl Display for GameVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            GameVersion::Demo1_6 => "1.6 (demo)",
+            GameVersion::V1_6_2 => "1.6.2",
+            GameVersion::V1_6_3b => "1.6.3b",
+            GameVersion::V1_7_6 => "1.7.6",
+            GameVersion::V1_8_4 => "1.8.4",
+            GameVersion::V1_9 => "1.9",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use binrw::BinRead;
+
+    use super::{FileVersion, GameVersion, Version};
+
+    #[rstest::rstest]
+    #[case::current(b"\xA1\x05\x00\x00", FileVersion::Known(GameVersion::V1_9))]
+    #[case::zeroed(b"\x00\x00\x00\x00", FileVersion::Zeroed)]
+    fn version_is_detected(#[case] bytes: &[u8; 4], #[case] expected: FileVersion) {
+        let mut reader = Cursor::new(bytes);
+        let version = Version::read_le(&mut reader).expect("Must read here");
+
+        assert_eq!(expected, version.file_version());
+    }
+
+    #[rstest::rstest]
+    #[case::demo(b"\x87\x05\x00\x00")]
+    #[case::v1_8_4(b"\xA0\x05\x00\x00")]
+    #[case::unknown(b"\x42\x05\x00\x00")]
+    #[case::garbage_upper_bytes(b"\xA1\xBE\xAD\xDE")]
+    #[case::garbage_top_byte(b"\xA1\x05\x00\x01")]
+    fn unsupported_version_returns_proper_error(#[case] bytes: &[u8; 4]) {
+        let expected_err = binary_file::UnsupportedVersion {
+            expected: 0x05A1,
+            actual: u16::from_le_bytes([bytes[0], bytes[1]]),
+        };
+
+        let mut reader = Cursor::new(bytes);
+        let err = Version::read_le(&mut reader).expect_err("Must fail here");
+
+        let actual_err = match binary_file::ErrorDetail::from(err) {
+            binary_file::ErrorDetail::UnsupportedVersion(error) => error,
+            other => panic!("Unexpected error variant: {other:?}"),
+        };
+
+        assert_eq!(expected_err, actual_err);
+    }
+}
@@ -1,5 +1,8 @@
+use crate::PlayerProgress;
+
 #[binrw::binrw]
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[brw(little)]
 pub struct SingleplayerWins {
     /// A total number of singleplayer wins for any character on any difficulty.
@@ -19,3 +22,110 @@ pub struct SingleplayerWins {
     /// Story mode for any character on any difficulty without dying.
     pub n_story_1ccs: u32,
 }
+
+/// One of the 1CC counters in [`SingleplayerWins`].
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display, enum_array::EnumMembersArray,
+)]
+pub enum OneCreditCounter {
+    #[display("Easy arcade 1CCs")]
+    ArcadeEasy,
+    #[display("Medium arcade 1CCs")]
+    ArcadeMedium,
+    #[display("Hard arcade 1CCs")]
+    ArcadeHard,
+    #[display("Story 1CCs")]
+    Story,
+}
+
+/// A counter is lower than the number of characters with a star,
+/// which can't happen in a normal playthrough.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
+#[display("{counter} counter is {value}, but {n_stars} characters have a star")]
+pub struct CounterMismatch {
+    pub counter: OneCreditCounter,
+    pub value: u32,
+    pub n_stars: usize,
+}
+
+impl SingleplayerWins {
+    pub fn one_credit_mut(&mut self, counter: OneCreditCounter) -> &mut u32 {
+        match counter {
+            OneCreditCounter::ArcadeEasy => &mut self.n_arcade_easy_1ccs,
+            OneCreditCounter::ArcadeMedium => &mut self.n_arcade_medium_1ccs,
+            OneCreditCounter::ArcadeHard => &mut self.n_arcade_hard_1ccs,
+            OneCreditCounter::Story => &mut self.n_story_1ccs,
+        }
+    }
+}
+
+impl PlayerProgress {
+    /// Counters that don't add up with the 1CC stars.
+    #[must_use]
+    pub fn counter_mismatches(&self) -> Vec<CounterMismatch> {
+        let pairs = [
+            (
+                OneCreditCounter::ArcadeEasy,
+                self.wins.n_arcade_easy_1ccs,
+                self.arcade_easy_1ccs.n_completed(),
+            ),
+            (
+                OneCreditCounter::ArcadeMedium,
+                self.wins.n_arcade_medium_1ccs,
+                self.arcade_medium_1ccs.n_completed(),
+            ),
+            (
+                OneCreditCounter::ArcadeHard,
+                self.wins.n_arcade_hard_1ccs,
+                self.arcade_hard_1ccs.n_completed(),
+            ),
+            (
+                OneCreditCounter::Story,
+                self.wins.n_story_1ccs,
+                self.story_1ccs.n_completed(),
+            ),
+        ];
+        pairs
+            .into_iter()
+            .filter(|&(_, value, n_stars)| {
+                usize::try_from(value).is_ok_and(|value| value < n_stars)
+            })
+            .map(|(counter, value, n_stars)| CounterMismatch {
+                counter,
+                value,
+                n_stars,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PerfectArcadeMode, PlayerProgress};
+
+    use super::{CounterMismatch, OneCreditCounter, SingleplayerWins};
+
+    #[rstest::rstest]
+    fn fresh_progress_is_consistent() {
+        assert!(PlayerProgress::default().counter_mismatches().is_empty());
+    }
+
+    #[rstest::rstest]
+    fn stars_without_runs_are_reported() {
+        let progress = PlayerProgress {
+            arcade_hard_1ccs: PerfectArcadeMode::COMPLETED,
+            wins: SingleplayerWins {
+                n_arcade_hard_1ccs: 3,
+                ..SingleplayerWins::default()
+            },
+            ..PlayerProgress::default()
+        };
+
+        let expected = vec![CounterMismatch {
+            counter: OneCreditCounter::ArcadeHard,
+            value: 3,
+            n_stars: PerfectArcadeMode::N_CHARACTERS,
+        }];
+        assert_eq!(expected, progress.counter_mismatches());
+    }
+}
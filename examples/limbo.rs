@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use anyhow::Context;
-use binary_file::ErroneousAction;
+use binary_file::{ErroneousAction, SaveFormat};
 use clap::Parser;
 
 #[derive(clap::Parser)]
@@ -1,19 +1,18 @@
 use std::{path::PathBuf, time::Instant};
 
-use ratatui::{
-    DefaultTerminal,
-    crossterm::{self, event::KeyCode},
-    widgets::Widget,
-};
+use aos2_env::AoS2Env;
+use ratatui::{DefaultTerminal, crossterm, widgets::Widget};
 
 use crate::{
     editor, limbo,
-    savefile::{self, Savefile},
+    savefile::{self, Savefile, backup::Retention, watcher::Watcher},
+    start,
     tui::{Event, HandleEvent, VisualComponent},
 };
 
 pub struct App {
     should_run: bool,
+    backups: Retention,
     screen: Screen,
     previous_event: Event,
 }
@@ -27,24 +26,33 @@ pub enum Error {
 }
 
 enum Screen {
-    Editor(editor::App),
+    Start(start::Screen),
+    Editor(Box<editor::App>),
     Limbo(limbo::Screen),
 }
 
 impl App {
     #[must_use]
-    pub fn from_env() -> Self {
-        match Savefile::from_env() {
-            Ok(savefile) => Self::new_editor(savefile),
-            Err(error) => Self::new_limbo(error),
+    pub fn from_env(backups: Retention) -> Self {
+        match AoS2Env::from_home_dir() {
+            Ok(env) => Self::new_start(env, backups),
+            Err(error) => Self::new_limbo(error.into()),
         }
     }
 
     #[must_use]
-    pub fn from_path(path: impl Into<PathBuf>) -> Self {
-        match Savefile::from_path(path) {
-            Ok(savefile) => Self::new_editor(savefile),
-            Err(error) => Self::new_limbo(error),
+    pub fn from_path(path: impl Into<PathBuf>, backups: Retention) -> Self {
+        Self::new_start(AoS2Env::from_path(path), backups)
+    }
+
+    /// Asks which save slot to edit first.
+    #[must_use]
+    pub fn new_start(env: AoS2Env, backups: Retention) -> Self {
+        Self {
+            should_run: true,
+            backups,
+            screen: Screen::Start(start::Screen::new(env, backups)),
+            previous_event: Event::empty(Instant::now()),
         }
     }
 
@@ -52,7 +60,8 @@ impl App {
     pub fn new_editor(savefile: Savefile) -> Self {
         Self {
             should_run: true,
-            screen: Screen::Editor(editor::App::new(savefile)),
+            backups: Retention::default(),
+            screen: Screen::Editor(Box::new(editor::App::new(savefile))),
             previous_event: Event::empty(Instant::now()),
         }
     }
@@ -61,6 +70,7 @@ impl App {
     pub fn new_limbo(error: savefile::Error) -> Self {
         Self {
             should_run: true,
+            backups: Retention::default(),
             screen: Screen::Limbo(limbo::Screen::new(error)),
             previous_event: Event::empty(Instant::now()),
         }
@@ -77,32 +87,77 @@ impl App {
     }
 
     fn handle_events(&mut self) -> Result<(), Error> {
-        let event = self.previous_event.clone().follow_with(
-            &crossterm::event::read().map_err(Error::Event)?,
-            Instant::now(),
-        );
+        // Doesn't wait for keys forever, so that the editor notices files written by the game.
+        if crossterm::event::poll(Watcher::INTERVAL).map_err(Error::Event)? {
+            let event = self.previous_event.clone().follow_with(
+                &crossterm::event::read().map_err(Error::Event)?,
+                Instant::now(),
+            );
+
+            self.handle_event(&event);
+
+            self.previous_event = event;
+        }
 
-        self.handle_event(&event);
+        if let Screen::Start(start) = &self.screen {
+            match start.outcome() {
+                Some(start::Outcome::Edit) => {
+                    self.screen = match start.open() {
+                        Ok(savefile) => Screen::Editor(Box::new(editor::App::new(savefile))),
+                        Err(error) => Screen::Limbo(self.new_limbo_screen(error, start.env())),
+                    };
+                }
+                Some(start::Outcome::Quit) => self.should_run = false,
+                None => (),
+            }
+        }
 
-        self.previous_event = event;
+        if let Screen::Limbo(limbo) = &self.screen {
+            match limbo.outcome() {
+                Some(limbo::Outcome::Open(env)) => {
+                    let env = env.clone();
+                    self.screen = match Savefile::load(env.clone()) {
+                        Ok(savefile) => Screen::Editor(Box::new(editor::App::new(
+                            savefile.with_backup_retention(self.backups),
+                        ))),
+                        Err(error) => Screen::Limbo(self.new_limbo_screen(error, &env)),
+                    };
+                }
+                Some(limbo::Outcome::Quit) => self.should_run = false,
+                None => (),
+            }
+        }
 
         if let Screen::Editor(editor) = &mut self.screen
             && let Err(error) = editor.handle_savefile_updates()
         {
-            self.screen = Screen::Limbo(limbo::Screen::new(error));
+            let env = editor.env().clone();
+            self.screen = Screen::Limbo(self.new_limbo_screen(error, &env));
+        }
+
+        if let Screen::Editor(editor) = &self.screen
+            && editor.is_finished()
+        {
+            self.should_run = false;
         }
 
         Ok(())
     }
+
+    fn new_limbo_screen(&self, error: savefile::Error, env: &AoS2Env) -> limbo::Screen {
+        limbo::Screen::new(error)
+            .with_env(env.clone())
+            .with_backup_retention(self.backups)
+    }
 }
 
 impl HandleEvent for App {
     fn handle_event(&mut self, event: &Event) {
         match (event.key_code(), &mut self.screen) {
-            (Some(KeyCode::Esc), _) => {
-                self.should_run = false;
-            }
+            // Editor decides on its own, because it may have unsaved changes.
             (_, Screen::Editor(editor)) => editor.handle_event(event),
+            // Esc may only cancel typing there.
+            (_, Screen::Start(screen)) => screen.handle_event(event),
             (_, Screen::Limbo(screen)) => screen.handle_event(event),
         }
     }
@@ -114,6 +169,7 @@ impl Widget for &App {
         Self: Sized,
     {
         match &self.screen {
+            Screen::Start(screen) => screen.render(area, buf),
             Screen::Editor(app) => app.render(area, buf),
             Screen::Limbo(screen) => screen.render(area, buf),
         }
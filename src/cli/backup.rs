@@ -0,0 +1,51 @@
+use crate::savefile::backup::Backups;
+
+use super::Location;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Show all backups, newest first.
+    List {
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Put files from a backup back into the saves folder.
+    ///
+    /// Current files are backed up first, so this can be undone.
+    Restore {
+        /// Backup name, as shown by `backup list`.
+        name: String,
+        #[command(flatten)]
+        location: Location,
+    },
+}
+
+impl Command {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self {
+            Command::List { location } => {
+                let env = location.env()?;
+                let backups = Backups::new(&env);
+
+                let snapshots = backups.list()?;
+                if snapshots.is_empty() {
+                    println!("No backups in {}", backups.folder().display());
+                }
+                for snapshot in snapshots {
+                    println!("{}", snapshot.timestamp);
+                }
+                Ok(())
+            }
+            Command::Restore { name, location } => {
+                let env = location.writable_env()?;
+                let backups = Backups::new(&env);
+
+                let snapshot = backups.find(&name)?;
+                backups.restore(&snapshot, &env)?;
+
+                println!("Restored `{name}` into {}", env.saves_folder.display());
+                Ok(())
+            }
+        }
+    }
+}
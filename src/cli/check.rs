@@ -0,0 +1,32 @@
+use binary_file::SaveFormat;
+use online_profile::PlayerOnlineProfile;
+use player_progress::PlayerProgress;
+
+use crate::savefile::check::{self, Severity};
+
+use super::Location;
+
+/// Prints what looks off in both files, and fails if the game is likely to crash.
+pub fn run(location: &Location) -> anyhow::Result<()> {
+    let env = location.env()?;
+    let progress = PlayerProgress::load(&env)?;
+    let profile = PlayerOnlineProfile::load(&env)?;
+
+    let issues = check::check(&progress, &profile);
+    for issue in &issues {
+        println!("{}: {issue}", issue.severity());
+    }
+
+    let n_errors = issues
+        .iter()
+        .filter(|issue| issue.severity() == Severity::Error)
+        .count();
+    match (issues.len(), n_errors) {
+        (0, _) => {
+            println!("All good in {}", env.saves_folder.display());
+            Ok(())
+        }
+        (_, 0) => Ok(()),
+        (_, n_errors) => anyhow::bail!("Found {n_errors} error(s) that may crash the game"),
+    }
+}
@@ -0,0 +1,132 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use binary_file::SaveFormat;
+use online_profile::PlayerOnlineProfile;
+use player_progress::PlayerProgress;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::savefile::FileKind;
+
+use super::Location;
+
+/// Text format of exported files. See `docs/save-schema.md`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Json,
+    Toml,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Export {
+    /// Which save file to export.
+    #[arg(value_enum)]
+    file: FileKind,
+    /// Where to write. Prints to the terminal if not set.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    /// Guessed from the output file extension, JSON otherwise.
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+    #[command(flatten)]
+    location: Location,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Import {
+    /// Which save file to replace.
+    #[arg(value_enum)]
+    file: FileKind,
+    /// Previously exported file.
+    input: PathBuf,
+    /// Guessed from the input file extension, JSON otherwise.
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+    #[command(flatten)]
+    location: Location,
+}
+
+impl Format {
+    fn guess(explicit: Option<Self>, path: Option<&Path>) -> Self {
+        let from_extension =
+            path.and_then(Path::extension)
+                .and_then(|extension| match extension.to_str()? {
+                    "toml" => Some(Format::Toml),
+                    "json" => Some(Format::Json),
+                    _ => None,
+                });
+        explicit.or(from_extension).unwrap_or(Format::Json)
+    }
+
+    fn serialize<T: Serialize>(self, value: &T) -> anyhow::Result<String> {
+        match self {
+            Format::Json => Ok(serde_json::to_string_pretty(value)?),
+            Format::Toml => Ok(toml::to_string_pretty(value)?),
+        }
+    }
+
+    fn deserialize<T: DeserializeOwned>(self, text: &str) -> anyhow::Result<T> {
+        match self {
+            Format::Json => Ok(serde_json::from_str(text)?),
+            Format::Toml => Ok(toml::from_str(text)?),
+        }
+    }
+}
+
+impl Export {
+    pub fn run(self) -> anyhow::Result<()> {
+        let Self {
+            file,
+            output,
+            format,
+            location,
+        } = self;
+        let env = location.env()?;
+        let format = Format::guess(format, output.as_deref());
+
+        let text = match file {
+            FileKind::Progress => format.serialize(&PlayerProgress::load(&env)?)?,
+            FileKind::Profile => format.serialize(&PlayerOnlineProfile::load(&env)?)?,
+        };
+
+        if let Some(path) = output {
+            std::fs::write(&path, text)
+                .with_context(|| format!("Failed to write `{}`", path.display()))
+        } else {
+            println!("{text}");
+            Ok(())
+        }
+    }
+}
+
+impl Import {
+    pub fn run(self) -> anyhow::Result<()> {
+        let Self {
+            file,
+            input,
+            format,
+            location,
+        } = self;
+        let env = location.writable_env()?;
+        let format = Format::guess(format, Some(&input));
+
+        let text = std::fs::read_to_string(&input)
+            .with_context(|| format!("Failed to read `{}`", input.display()))?;
+        let context = || format!("`{}` is not a valid {}", input.display(), file.file_name());
+        match file {
+            FileKind::Progress => {
+                super::write_progress(&env, &format.deserialize(&text).with_context(context)?)?;
+            }
+            FileKind::Profile => {
+                super::write_profile(&env, &format.deserialize(&text).with_context(context)?)?;
+            }
+        }
+
+        println!(
+            "Imported `{}` into {}",
+            input.display(),
+            env.saves_folder.join(file.file_name()).display()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+//! Headless commands that do their job and exit without starting the TUI.
+
+mod backup;
+mod check;
+mod export;
+mod name;
+mod preset;
+mod profile;
+mod progress;
+mod salvage;
+mod slot;
+
+use std::path::PathBuf;
+
+use aos2_env::{
+    AoS2Env,
+    discovery::Discovery,
+    process::{self, ProcFs},
+};
+use binary_file::SaveFormat;
+use online_profile::PlayerOnlineProfile;
+use player_progress::PlayerProgress;
+
+use crate::savefile::backup::Backups;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Manage backups that the editor makes before touching save files.
+    #[command(subcommand)]
+    Backup(backup::Command),
+    /// Unlock everything in a category.
+    Unlock {
+        #[arg(value_enum)]
+        what: progress::Unlockables,
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Lock a single character, arena or music track.
+    #[command(subcommand)]
+    Lock(progress::Lock),
+    /// Change online profile appearance.
+    #[command(subcommand)]
+    Set(profile::Set),
+    /// Create fresh save files, like the game does on first launch.
+    ///
+    /// Only missing files are created, existing ones stay as is.
+    Init {
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Update an online profile with an older layout or version.
+    Migrate {
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Look for things that may crash the game, exits with an error if any.
+    Check {
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Read damaged save files anyway, listing every part that doesn't make sense.
+    Salvage(salvage::Salvage),
+    /// Save and apply named sets of profile and progress fields.
+    #[command(subcommand)]
+    Preset(preset::Command),
+    /// Keep several sets of save files and swap which one the game sees.
+    #[command(subcommand)]
+    Slot(slot::Command),
+    /// Write a save file as human-readable JSON or TOML.
+    Export(export::Export),
+    /// Replace a save file with a previously exported one.
+    Import(export::Import),
+    /// List every place the saves folder may be, best first, and why each is considered.
+    ///
+    /// The first one is used when `--saves-folder` isn't given.
+    Locate,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Location {
+    /// Path to saves folder (ends with `Documents/Fruitbat Factory/AoS2`).
+    #[arg(long, global = true)]
+    saves_folder: Option<PathBuf>,
+    /// Write save files even while the game is running.
+    #[arg(long, global = true)]
+    even_if_running: bool,
+}
+
+/// The game writes its own copy of the files on exit, so edits made meanwhile are lost.
+#[derive(Debug, thiserror::Error)]
+#[error("AoS2 is running (pid {pid}), close it first or add `--even-if-running`")]
+pub struct GameIsRunning {
+    pub pid: u32,
+}
+
+impl Command {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self {
+            Command::Backup(command) => command.run(),
+            Command::Unlock { what, location } => progress::unlock(what, &location),
+            Command::Lock(command) => command.run(),
+            Command::Set(command) => command.run(),
+            Command::Init { location } => init(&location),
+            Command::Migrate { location } => profile::migrate(&location),
+            Command::Check { location } => check::run(&location),
+            Command::Salvage(command) => command.run(),
+            Command::Preset(command) => command.run(),
+            Command::Slot(command) => command.run(),
+            Command::Export(command) => command.run(),
+            Command::Import(command) => command.run(),
+            Command::Locate => {
+                locate();
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Location {
+    pub fn env(&self) -> anyhow::Result<AoS2Env> {
+        match &self.saves_folder {
+            Some(path) => Ok(AoS2Env::from_path(path)),
+            None => Ok(AoS2Env::from_home_dir()?),
+        }
+    }
+
+    /// Same as [`Location::env`], but refuses while the game is running, unless asked not to.
+    pub fn writable_env(&self) -> anyhow::Result<AoS2Env> {
+        if !self.even_if_running
+            && let Some(game) = process::find_game(&ProcFs::default())
+        {
+            return Err(GameIsRunning { pid: game.pid }.into());
+        }
+        self.env()
+    }
+}
+
+impl GameIsRunning {
+    /// Differs from other failures (1) and bad arguments (2), so scripts can wait and retry.
+    pub const EXIT_CODE: i32 = 3;
+}
+
+fn init(location: &Location) -> anyhow::Result<()> {
+    let env = location.writable_env()?;
+    let created = crate::savefile::create_missing(&env)?;
+
+    if created.is_empty() {
+        println!("Save files already exist in {}", env.saves_folder.display());
+    }
+    for file in created {
+        println!(
+            "Created {}",
+            env.saves_folder.join(file.file_name()).display()
+        );
+    }
+    Ok(())
+}
+
+fn locate() {
+    let candidates = Discovery::from_env().candidates();
+    if candidates.is_empty() {
+        println!("Nowhere to look, {}", aos2_env::Error::Home);
+    }
+    for (index, candidate) in candidates.iter().enumerate() {
+        println!("{}. {candidate}", index + 1);
+    }
+}
+
+/// Loads the progress file, edits it and writes it back, with a backup in between.
+fn edit_progress(env: &AoS2Env, edit: impl FnOnce(&mut PlayerProgress)) -> anyhow::Result<()> {
+    let mut progress = PlayerProgress::load(env)?;
+    edit(&mut progress);
+    write_progress(env, &progress)
+}
+
+/// Same as [`edit_progress`], but for the online profile.
+fn edit_profile(env: &AoS2Env, edit: impl FnOnce(&mut PlayerOnlineProfile)) -> anyhow::Result<()> {
+    let mut profile = PlayerOnlineProfile::load(env)?;
+    edit(&mut profile);
+    write_profile(env, &profile)
+}
+
+/// Replaces the progress file, backing up the old one.
+fn write_progress(env: &AoS2Env, progress: &PlayerProgress) -> anyhow::Result<()> {
+    Backups::new(env).snapshot(env)?;
+    progress.save(env)?;
+    Ok(())
+}
+
+/// Replaces the online profile file, backing up the old one.
+fn write_profile(env: &AoS2Env, profile: &PlayerOnlineProfile) -> anyhow::Result<()> {
+    Backups::new(env).snapshot(env)?;
+    profile.save(env)?;
+    Ok(())
+}
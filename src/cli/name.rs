@@ -0,0 +1,118 @@
+//! Case-insensitive parsing of game names for command arguments.
+
+use std::fmt::{Debug, Display};
+
+use online_profile::{Visibility, avatar, title};
+use player_progress::{Arena, Character, MusicTrack};
+
+/// Enum that can be found by its name on the command line.
+pub trait Named: Sized + Copy + Debug + Display + Send + Sync + 'static {
+    /// What the enum stands for, to be shown in errors.
+    const KIND: &'static str;
+
+    fn members() -> Vec<Self>;
+}
+
+/// Lists with more names than this are too long to show in a single error.
+const MAX_SUGGESTIONS: usize = 32;
+
+/// Finds an enum member by either its display name or variant name.
+///
+/// Case, spaces and punctuation are ignored,
+/// so `star-breaker`, `StarBreaker` and `"Star Breaker"` are all the same.
+pub fn parse<T: Named>(input: &str) -> Result<T, String> {
+    let wanted = normalize(input);
+
+    let members = T::members();
+    if let Some(&member) = members.iter().find(|member| {
+        normalize(&member.to_string()) == wanted || normalize(&format!("{member:?}")) == wanted
+    }) {
+        return Ok(member);
+    }
+
+    let similar: Vec<String> = members
+        .iter()
+        .map(ToString::to_string)
+        .filter(|name| members.len() <= MAX_SUGGESTIONS || normalize(name).contains(&wanted))
+        .take(MAX_SUGGESTIONS)
+        .collect();
+    if similar.is_empty() {
+        Err(format!("no {} named `{input}`", T::KIND))
+    } else {
+        Err(format!(
+            "no {} named `{input}`, try one of: {}",
+            T::KIND,
+            similar.join(", ")
+        ))
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|char| char.to_ascii_lowercase())
+        .collect()
+}
+
+macro_rules! impl_named {
+    ($($t:ty => $kind:literal),* $(,)?) => {
+        $(
+            impl Named for $t {
+                const KIND: &'static str = $kind;
+
+                fn members() -> Vec<Self> {
+                    <$t>::members().to_vec()
+                }
+            }
+        )*
+    };
+}
+
+impl_named! {
+    Character => "character",
+    Arena => "arena",
+    MusicTrack => "music track",
+    title::Text => "title",
+    title::Color => "title color",
+    title::Character => "title background character",
+    avatar::Character => "avatar",
+    avatar::Background => "avatar background",
+    Visibility => "visibility",
+}
+
+#[cfg(test)]
+mod tests {
+    use online_profile::{Visibility, avatar};
+    use player_progress::{Character, MusicTrack};
+
+    use super::parse;
+
+    #[rstest::rstest]
+    #[case("star breaker", Character::StarBreaker)]
+    #[case("StarBreaker", Character::StarBreaker)]
+    #[case("STAR-BREAKER", Character::StarBreaker)]
+    #[case("sora", Character::Sora)]
+    fn parses_characters(#[case] input: &str, #[case] expected: Character) {
+        assert_eq!(Ok(expected), parse(input));
+    }
+
+    #[rstest::rstest]
+    fn parses_display_names_with_symbols() {
+        assert_eq!(Ok(avatar::Character::OjAlte), parse("100% alte"));
+        assert_eq!(Ok(avatar::Character::OjAlte), parse("ojalte"));
+    }
+
+    #[rstest::rstest]
+    #[case("show", Visibility::Show)]
+    #[case("hidden", Visibility::Hide)]
+    fn parses_visibility(#[case] input: &str, #[case] expected: Visibility) {
+        assert_eq!(Ok(expected), parse(input));
+    }
+
+    #[rstest::rstest]
+    fn unknown_name_lists_options() {
+        let error = parse::<MusicTrack>("bad song").expect_err("Must not parse");
+
+        assert!(error.contains("Black Hole"));
+    }
+}
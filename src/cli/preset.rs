@@ -0,0 +1,84 @@
+use binary_file::SaveFormat;
+use online_profile::PlayerOnlineProfile;
+use player_progress::PlayerProgress;
+
+use crate::savefile::preset::{Preset, Presets};
+
+use super::Location;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Show all presets by name.
+    List {
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Remember how the online profile looks now under a name.
+    ///
+    /// That's avatar, title and visibility settings. Overwrites a preset with the same name.
+    Save {
+        name: String,
+        /// Also remember enabled characters, arenas and music.
+        #[arg(long)]
+        with_progress: bool,
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Put preset fields on top of save files, leaving everything else as is.
+    Apply {
+        /// Preset name, as shown by `preset list`.
+        name: String,
+        #[command(flatten)]
+        location: Location,
+    },
+}
+
+impl Command {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self {
+            Command::List { location } => {
+                let env = location.env()?;
+                let presets = Presets::new(&env);
+
+                let names = presets.list()?;
+                if names.is_empty() {
+                    println!("No presets in {}", presets.folder().display());
+                }
+                for name in names {
+                    println!("{name}");
+                }
+                Ok(())
+            }
+            Command::Save {
+                name,
+                with_progress,
+                location,
+            } => {
+                let env = location.env()?;
+                let mut preset = Preset::from_profile(&PlayerOnlineProfile::load(&env)?);
+                if with_progress {
+                    preset = preset.with_progress(&PlayerProgress::load(&env)?);
+                }
+
+                let path = Presets::new(&env).save(&name, &preset)?;
+
+                println!("Saved `{name}` to {}", path.display());
+                Ok(())
+            }
+            Command::Apply { name, location } => {
+                let env = location.writable_env()?;
+                let preset = Presets::new(&env).load(&name)?;
+
+                if preset.has_progress_fields() {
+                    super::edit_progress(&env, |progress| preset.apply_to_progress(progress))?;
+                }
+                if preset.has_profile_fields() {
+                    super::edit_profile(&env, |profile| preset.apply_to_profile(profile))?;
+                }
+
+                println!("Applied `{name}` to {}", env.saves_folder.display());
+                Ok(())
+            }
+        }
+    }
+}
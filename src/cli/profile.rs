@@ -0,0 +1,163 @@
+use binary_file::SaveFormat;
+use online_profile::{
+    PlayerOnlineProfile, Visibility, avatar, title,
+    version::{KnownLayout, KnownVersion},
+};
+
+use super::{Location, name};
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Set {
+    /// Change the title shown to other players online.
+    Title {
+        #[arg(value_parser = name::parse::<title::Text>)]
+        text: title::Text,
+        #[arg(long, value_parser = name::parse::<title::Color>)]
+        color: Option<title::Color>,
+        /// Character in the title background.
+        #[arg(long, value_parser = name::parse::<title::Character>)]
+        character: Option<title::Character>,
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Change the avatar shown to other players online.
+    Avatar {
+        #[arg(value_parser = name::parse::<avatar::Character>)]
+        character: avatar::Character,
+        #[arg(long, value_parser = name::parse::<avatar::Background>)]
+        background: Option<avatar::Background>,
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Show or hide things other players see online. Options not given stay as they are.
+    ///
+    /// Each option takes `show` or `hide`.
+    Visibility {
+        /// Your flag next to the nickname.
+        #[arg(long, value_parser = name::parse::<Visibility>)]
+        country: Option<Visibility>,
+        /// Your title during matches.
+        #[arg(long, value_parser = name::parse::<Visibility>)]
+        ingame_title: Option<Visibility>,
+        /// Combo and hitstun info during matches.
+        #[arg(long, value_parser = name::parse::<Visibility>)]
+        hitstun_meter: Option<Visibility>,
+        /// Whether others can watch your matches.
+        #[arg(long, value_parser = name::parse::<Visibility>)]
+        spectators: Option<Visibility>,
+        #[command(flatten)]
+        location: Location,
+    },
+}
+
+impl Set {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self {
+            Set::Title {
+                text,
+                color,
+                character,
+                location,
+            } => {
+                let env = location.writable_env()?;
+                super::edit_profile(&env, |profile| {
+                    profile.title_text_id = text;
+                    if let Some(color) = color {
+                        profile.title_color = color;
+                    }
+                    if let Some(character) = character {
+                        profile.title_character_in_background = character;
+                    }
+                })?;
+
+                println!("Title set to {text} in {}", env.saves_folder.display());
+                Ok(())
+            }
+            Set::Avatar {
+                character,
+                background,
+                location,
+            } => {
+                let env = location.writable_env()?;
+                super::edit_profile(&env, |profile| {
+                    profile.avatar_character = character;
+                    if let Some(background) = background {
+                        profile.avatar_background = background;
+                    }
+                })?;
+
+                println!(
+                    "Avatar set to {character} in {}",
+                    env.saves_folder.display()
+                );
+                Ok(())
+            }
+            Set::Visibility {
+                country,
+                ingame_title,
+                hitstun_meter,
+                spectators,
+                location,
+            } => {
+                let options = [
+                    ("country", country),
+                    ("in-game title", ingame_title),
+                    ("hitstun meter", hitstun_meter),
+                    ("spectators", spectators),
+                ];
+                if options.iter().all(|(_, shown)| shown.is_none()) {
+                    anyhow::bail!(
+                        "Nothing to change, pass at least one of `--country`, `--ingame-title`, `--hitstun-meter`, `--spectators`"
+                    );
+                }
+                let env = location.writable_env()?;
+                super::edit_profile(&env, |profile| {
+                    let fields = [
+                        &mut profile.country,
+                        &mut profile.ingame_title,
+                        &mut profile.hitstun_meter,
+                        &mut profile.spectators,
+                    ];
+                    for (field, (_, shown)) in fields.into_iter().zip(options) {
+                        if let Some(shown) = shown {
+                            *field = shown;
+                        }
+                    }
+                })?;
+
+                let changes: Vec<String> = options
+                    .iter()
+                    .filter_map(|(name, shown)| Some(format!("{name}: {}", (*shown)?)))
+                    .collect();
+                println!(
+                    "Set {} in {}",
+                    changes.join(", "),
+                    env.saves_folder.display()
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Brings the online profile to the current game version, keeping unlocks.
+pub fn migrate(location: &Location) -> anyhow::Result<()> {
+    let env = location.writable_env()?;
+    let profile = PlayerOnlineProfile::load(&env)?;
+    if profile.is_up_to_date() {
+        println!("Profile is already up to date");
+        return Ok(());
+    }
+
+    let mut migrated = profile;
+    migrated.migrate();
+    super::write_profile(&env, &migrated)?;
+
+    println!(
+        "Profile migrated to game {}, {} in {}",
+        KnownVersion::CURRENT,
+        KnownLayout::CURRENT,
+        env.saves_folder.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use player_progress::{
+    Arena, Arenas, Character, MusicTrack, MusicTracks, PlayableCharacters, Status,
+};
+
+use super::{Location, name};
+
+/// What to unlock all at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Unlockables {
+    Characters,
+    Arenas,
+    Music,
+    All,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Lock {
+    /// Make a character unavailable at character select.
+    Character {
+        #[arg(value_parser = name::parse::<Character>)]
+        name: Character,
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Hide an arena background.
+    Arena {
+        #[arg(value_parser = name::parse::<Arena>)]
+        name: Arena,
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Hide a music track.
+    Music {
+        #[arg(value_parser = name::parse::<MusicTrack>)]
+        name: MusicTrack,
+        #[command(flatten)]
+        location: Location,
+    },
+}
+
+pub fn unlock(what: Unlockables, location: &Location) -> anyhow::Result<()> {
+    let env = location.writable_env()?;
+    super::edit_progress(&env, |progress| {
+        if matches!(what, Unlockables::Characters | Unlockables::All) {
+            progress.playable_characters = PlayableCharacters::ALL;
+        }
+        if matches!(what, Unlockables::Arenas | Unlockables::All) {
+            progress.arenas = Arenas::ALL;
+        }
+        if matches!(what, Unlockables::Music | Unlockables::All) {
+            progress.music_tracks = MusicTracks::ALL;
+        }
+    })?;
+
+    println!("Unlocked {what:?} in {}", env.saves_folder.display());
+    Ok(())
+}
+
+impl Lock {
+    pub fn run(self) -> anyhow::Result<()> {
+        let (location, name) = match &self {
+            Lock::Character { name, location } => (location, name.to_string()),
+            Lock::Arena { name, location } => (location, name.to_string()),
+            Lock::Music { name, location } => (location, name.to_string()),
+        };
+        let env = location.writable_env()?;
+
+        super::edit_progress(&env, |progress| match self {
+            Lock::Character { name, .. } => progress.playable_characters[name] = Status::Disabled,
+            Lock::Arena { name, .. } => progress.arenas[name] = Status::Disabled,
+            Lock::Music { name, .. } => progress.music_tracks[name] = Status::Disabled,
+        })?;
+
+        println!("Locked `{name}` in {}", env.saves_folder.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::savefile::{FileKind, backup::Backups, salvage::Salvaged};
+
+use super::Location;
+
+#[derive(Debug, clap::Args)]
+pub struct Salvage {
+    /// Which save file to read, both if not set.
+    #[arg(value_enum)]
+    file: Option<FileKind>,
+    /// Replace damaged parts with fresh values, after a backup.
+    #[arg(long)]
+    fix: bool,
+    #[command(flatten)]
+    location: Location,
+}
+
+impl Salvage {
+    pub fn run(self) -> anyhow::Result<()> {
+        let Self {
+            file,
+            fix,
+            location,
+        } = self;
+        let env = if fix {
+            location.writable_env()?
+        } else {
+            location.env()?
+        };
+        let files = match file {
+            Some(file) => vec![file],
+            None => vec![FileKind::Progress, FileKind::Profile],
+        };
+
+        let mut n_damaged = 0;
+        for file in files {
+            let salvaged = Salvaged::read(file, &env)?;
+            println!("{}", salvaged.report());
+            if !salvaged.report().is_damaged() {
+                continue;
+            }
+
+            if fix {
+                let (_, backup) = salvaged.write(&env, &Backups::new(&env))?;
+                println!(
+                    "Repaired, the damaged file and this report are in {}",
+                    backup.folder.display()
+                );
+            } else {
+                n_damaged += 1;
+            }
+        }
+
+        if n_damaged > 0 {
+            anyhow::bail!("Found {n_damaged} damaged file(s), add `--fix` to repair them");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::savefile::{
+    backup::Backups,
+    slot::{Slots, Summary},
+};
+
+use super::Location;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Show all save slots with a short summary of each.
+    List {
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Copy files from the saves folder into a slot, overwriting it if it exists.
+    Store {
+        name: String,
+        #[command(flatten)]
+        location: Location,
+    },
+    /// Put a slot into the saves folder.
+    ///
+    /// The live slot gets the current files back first, and they're backed up too.
+    Switch {
+        /// Slot name, as shown by `slot list`.
+        name: String,
+        #[command(flatten)]
+        location: Location,
+    },
+}
+
+impl Command {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self {
+            Command::List { location } => {
+                let env = location.env()?;
+                let slots = Slots::new(&env);
+
+                let all = slots.list()?;
+                if all.is_empty() {
+                    println!("No slots in {}", slots.folder().display());
+                }
+                let live = slots.live();
+                for slot in all {
+                    let is_live = live.as_deref() == Some(slot.name.as_str());
+                    // The live slot is only up to date in the saves folder.
+                    let summary = if is_live {
+                        Summary::load(&env)
+                    } else {
+                        Summary::load(&slot.env())
+                    };
+                    let marker = if is_live { " (live)" } else { "" };
+                    match summary {
+                        Ok(Summary {
+                            nickname,
+                            n_enabled_characters,
+                            n_characters,
+                            title,
+                        }) => println!(
+                            "{}{marker}: {nickname}, {n_enabled_characters}/{n_characters} characters, title {title}",
+                            slot.name
+                        ),
+                        Err(error) => println!("{}{marker}: can't read: {error}", slot.name),
+                    }
+                }
+                Ok(())
+            }
+            Command::Store { name, location } => {
+                let env = location.env()?;
+                let slot = Slots::new(&env).store(&name, &env)?;
+
+                println!("Stored {} as `{name}`", env.saves_folder.display());
+                println!("Slot files are in {}", slot.folder.display());
+                Ok(())
+            }
+            Command::Switch { name, location } => {
+                let env = location.writable_env()?;
+                Slots::new(&env).switch_to(&name, &env, &Backups::new(&env))?;
+
+                println!("`{name}` is live in {}", env.saves_folder.display());
+                Ok(())
+            }
+        }
+    }
+}
@@ -20,6 +20,10 @@ impl<T, const LENGTH: usize> SelectableArray<T, LENGTH> {
         self.items.iter()
     }
 
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.items.iter_mut()
+    }
+
     pub fn current_index(&self) -> usize {
         self.current_index
     }
@@ -9,7 +9,7 @@ use ratatui::{
 use crate::{
     collection::SelectableArray,
     editor,
-    savefile::Savefile,
+    savefile::{FileKind, Savefile, UnsavedChanges},
     tui::{Event, HandleEvent, InteractibleComponent, VisualComponent},
     widget::content_box::ContentBox,
 };
@@ -18,23 +18,32 @@ use self::tabs::EvenTabs;
 
 pub trait InteratibleTabComponent: InteractibleComponent {
     fn name(&self) -> &'static str;
+
+    /// Files this tab shows or edits.
+    fn files(&self) -> &'static [FileKind];
 }
 
 pub struct ContentWidget {
-    tabs: SelectableArray<Box<dyn InteratibleTabComponent>, 4>,
+    tabs: SelectableArray<Box<dyn InteratibleTabComponent>, 8>,
+    unsaved_changes: UnsavedChanges,
 }
 
 impl ContentWidget {
     #[must_use]
     pub fn new(savefile: &Savefile) -> Self {
-        let tabs: [Box<dyn InteratibleTabComponent>; 4] = [
+        let tabs: [Box<dyn InteratibleTabComponent>; 8] = [
             Box::new(editor::statistics::Tab::new(savefile)),
             Box::new(editor::progress::Tab::new(savefile)),
             Box::new(editor::profile::avatar::Tab::new(savefile)),
             Box::new(editor::profile::title::Tab::new(savefile)),
+            Box::new(editor::profile::unlocks::Tab::new(savefile)),
+            Box::new(editor::profile::lobby::Tab::new(savefile)),
+            Box::new(editor::profile::options::Tab::new(savefile)),
+            Box::new(editor::presets::Tab::new(savefile)),
         ];
         Self {
             tabs: SelectableArray::new(tabs),
+            unsaved_changes: savefile.read_unsaved_changes(),
         }
     }
 }
@@ -55,7 +64,19 @@ impl VisualComponent for ContentWidget {
         let layout = Layout::vertical(constraints);
         let [tabs_area, content_area] = layout.areas::<2>(area);
 
-        EvenTabs::new(self.tabs.iter().map(|tab| tab.name()))
+        let tab_names = self.tabs.iter().map(|tab| {
+            let is_dirty = tab
+                .files()
+                .iter()
+                .any(|&file| self.unsaved_changes.is_dirty(file));
+            if is_dirty {
+                format!("{} *", tab.name())
+            } else {
+                tab.name().to_owned()
+            }
+        });
+
+        EvenTabs::new(tab_names)
             .select(self.tabs.current_index())
             .render(tabs_area, buf);
 
@@ -150,6 +150,13 @@ fn draw_controls(area: Rect, buf: &mut Buffer) {
         line("Enter", "Interact with selected item"),
         line("PgUp / PgDown", "Switch tabs"),
         line("Home / End", "Go to start/end of the list"),
+        line("Tab", "Show/hide lobby password"),
+        line("Space", "Toggle online option"),
+        line("F5 / F6", "Unlock/lock all shown (Online Unlocks tab)"),
+        line("F5", "Reload presets (Presets tab)"),
+        line("Ctrl + S", "Save changes"),
+        line("Ctrl + Z", "Undo"),
+        line("Ctrl + Y", "Redo"),
         line("Escape", "Exit"),
     ];
     Paragraph::new(lines).render(area, buf);
@@ -158,14 +165,14 @@ fn draw_controls(area: Rect, buf: &mut Buffer) {
 fn draw_extra_info(area: Rect, buf: &mut Buffer) {
     let lines = [
         Line::from(vec![
-            Span::raw("All "),
-            Span::raw("changes are saved automatically")
-                .style(Style::new().with_fg(IndexedColor::DarkYellow)),
-            Span::raw(" when you make them"),
+            Span::raw("Nothing is saved until you press "),
+            Span::raw("Ctrl + S").style(Style::new().with_fg(IndexedColor::DarkYellow)),
         ]),
+        Line::from("Tabs with unsaved changes are marked with `*`"),
         Line::from(""),
         Line::from("Close the game before editing"),
         Line::from("Otherwise, it will ignore your changes"),
+        Line::from("Files the game writes meanwhile are picked up"),
         Line::from(""),
         Line::from("If any issues occur, report them on GitHub"),
     ];
@@ -1,14 +1,33 @@
 pub mod content_window;
 pub mod info;
+pub mod presets;
 pub mod profile;
 pub mod progress;
 pub mod statistics;
 
-use ratatui::{buffer::Buffer, layout::Rect};
+use std::time::{Duration, Instant};
+
+use aos2_env::{
+    AoS2Env,
+    process::{self, ProcFs, Process},
+};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Widget,
+};
 
 use crate::{
-    savefile::{self, Savefile},
+    savefile::{
+        self, Savefile, check,
+        watcher::{ChangedBy, Conflict, Resolution},
+    },
+    style::{IndexedColor, WithColor},
     tui::{Event, HandleEvent, VisualComponent},
+    widget::dialog::Dialog,
 };
 
 use self::{content_window::ContentWidget, info::FullHelpToggle};
@@ -17,31 +36,383 @@ use self::{content_window::ContentWidget, info::FullHelpToggle};
 pub struct App {
     content: FullHelpToggle<ContentWidget>,
     savefile: Savefile,
+    mode: Mode,
+    /// Files changed both here and on disk, the first one is being asked about.
+    conflicts: Vec<Conflict>,
+    processes: ProcFs,
+    running_game: Option<Process>,
+    game_checked_at: Option<Instant>,
+    /// Waits here until every conflict is resolved.
+    pending_save: Option<SaveRequest>,
+    is_finished: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SaveRequest {
+    then_exit: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+enum Mode {
+    #[default]
+    Editing,
+    ConfirmingExit,
+    /// The game would overwrite the save on exit, so ask before writing it.
+    ConfirmingRunningGame {
+        then_exit: bool,
+    },
+    /// The save looks off, so ask before writing it.
+    ConfirmingSave {
+        issues: Vec<check::Issue>,
+        then_exit: bool,
+    },
 }
 
 impl App {
+    pub const SAVE_KEY: char = 's';
+    pub const UNDO_KEY: char = 'z';
+    pub const REDO_KEY: char = 'y';
+    /// Conflicts with more fields than this are cut, to fit the dialog on screen.
+    const MAX_CONFLICT_ROWS: usize = 12;
+    /// Listing processes reads a lot of files, so not on every key press.
+    const GAME_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
     pub fn new(savefile: Savefile) -> Self {
         Self {
             content: FullHelpToggle::new(ContentWidget::new(&savefile)),
             savefile,
+            mode: Mode::default(),
+            conflicts: Vec::new(),
+            processes: ProcFs::default(),
+            running_game: None,
+            game_checked_at: None,
+            pending_save: None,
+            is_finished: false,
         }
     }
 
+    #[must_use]
+    pub fn env(&self) -> &AoS2Env {
+        self.savefile.env()
+    }
+
+    /// Picks up files written by the game, and writes changes to disk, but only when asked to.
+    ///
+    /// Saving waits until every conflict is resolved, so nothing on disk is overwritten unseen.
     pub fn handle_savefile_updates(&mut self) -> Result<(), savefile::Error> {
-        self.savefile.save_all()?;
+        let now = Instant::now();
+        let is_check_due = self
+            .game_checked_at
+            .is_none_or(|checked_at| now.duration_since(checked_at) >= Self::GAME_CHECK_INTERVAL);
+        if is_check_due {
+            self.check_running_game(now);
+        }
+
+        for conflict in self.savefile.sync_with_disk().conflicts {
+            // The newest write of a file is all that matters.
+            self.conflicts
+                .retain(|pending| pending.file != conflict.file);
+            self.conflicts.push(conflict);
+        }
+
+        if self.conflicts.is_empty()
+            && let Some(SaveRequest { then_exit }) = self.pending_save.take()
+        {
+            self.savefile.save_all()?;
+            self.is_finished = then_exit;
+        }
 
         Ok(())
     }
+
+    fn resolve_conflict(&mut self, event: &Event) {
+        let Some(conflict) = self.conflicts.first() else {
+            return;
+        };
+        let resolution = match event.key_code() {
+            Some(KeyCode::Char('m' | 'M')) if conflict.can_merge() => Resolution::Merge,
+            Some(KeyCode::Char('k' | 'K')) => Resolution::KeepYours,
+            Some(KeyCode::Char('d' | 'D')) => Resolution::TakeDisk,
+            _ => return,
+        };
+        let conflict = self.conflicts.remove(0);
+        self.savefile.resolve(conflict, resolution);
+    }
+
+    fn check_running_game(&mut self, now: Instant) {
+        self.running_game = process::find_game(&self.processes);
+        self.game_checked_at = Some(now);
+    }
+
+    /// Asks first while the game is running, otherwise goes on to [`App::check_and_save`].
+    fn request_save(&mut self, then_exit: bool) {
+        // The banner may be a couple of seconds late, the save must not.
+        self.check_running_game(Instant::now());
+        if self.running_game.is_some() {
+            self.mode = Mode::ConfirmingRunningGame { then_exit };
+        } else {
+            self.check_and_save(then_exit);
+        }
+    }
+
+    /// Saves right away if everything looks fine, otherwise asks first.
+    fn check_and_save(&mut self, then_exit: bool) {
+        let issues = self.savefile.check();
+        if issues.is_empty() {
+            self.pending_save = Some(SaveRequest { then_exit });
+        } else {
+            self.mode = Mode::ConfirmingSave { issues, then_exit };
+        }
+    }
+
+    /// The user is done with editing, and all they wanted to save is saved.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.is_finished
+    }
 }
 
 impl HandleEvent for App {
     fn handle_event(&mut self, event: &Event) {
-        self.content.handle_event(event);
+        if !self.conflicts.is_empty() {
+            self.resolve_conflict(event);
+            return;
+        }
+
+        match &self.mode {
+            Mode::Editing => match (event.key_code(), event.ctrl_char()) {
+                (_, Some(Self::SAVE_KEY)) => self.request_save(false),
+                (_, Some(Self::UNDO_KEY)) => {
+                    self.savefile.undo();
+                }
+                (_, Some(Self::REDO_KEY)) => {
+                    self.savefile.redo();
+                }
+                (Some(KeyCode::Esc), _) if self.savefile.has_unsaved_changes() => {
+                    self.mode = Mode::ConfirmingExit;
+                }
+                (Some(KeyCode::Esc), _) => self.is_finished = true,
+                _ => self.content.handle_event(event),
+            },
+            Mode::ConfirmingExit => match event.key_code() {
+                Some(KeyCode::Char('y' | 'Y') | KeyCode::Enter) => {
+                    self.mode = Mode::Editing;
+                    self.request_save(true);
+                }
+                Some(KeyCode::Char('n' | 'N')) => self.is_finished = true,
+                Some(KeyCode::Esc) => self.mode = Mode::Editing,
+                _ => (),
+            },
+            &Mode::ConfirmingRunningGame { then_exit } => match event.key_code() {
+                Some(KeyCode::Char('y' | 'Y')) => {
+                    self.mode = Mode::Editing;
+                    self.check_and_save(then_exit);
+                }
+                Some(KeyCode::Char('n' | 'N') | KeyCode::Esc) => self.mode = Mode::Editing,
+                _ => (),
+            },
+            &Mode::ConfirmingSave { then_exit, .. } => match event.key_code() {
+                Some(KeyCode::Char('y' | 'Y')) => {
+                    self.pending_save = Some(SaveRequest { then_exit });
+                    self.mode = Mode::Editing;
+                }
+                Some(KeyCode::Char('n' | 'N') | KeyCode::Esc) => self.mode = Mode::Editing,
+                _ => (),
+            },
+        }
     }
 }
 
 impl VisualComponent for App {
     fn render(&self, area: Rect, buf: &mut Buffer) {
         self.content.render(area, buf);
+
+        if let Some(game) = &self.running_game {
+            // Over the title, it's the first thing to see.
+            Line::from(format!(
+                "AoS2 is running (pid {}), close it before saving or it will overwrite your changes",
+                game.pid
+            ))
+            .style(
+                Style::new()
+                    .with_bg(IndexedColor::BrightRed)
+                    .with_fg(Color::White),
+            )
+            .centered()
+            .render(Rect { height: 1, ..area }, buf);
+        }
+
+        let key = |text| Span::raw(text).style(Style::new().with_fg(IndexedColor::DarkYellow));
+        if let Mode::ConfirmingSave { issues, .. } = &self.mode {
+            let issue_lines = issues.iter().map(|issue| {
+                let color = match issue.severity() {
+                    check::Severity::Error => IndexedColor::BrightRed,
+                    check::Severity::Warning => IndexedColor::DarkYellow,
+                };
+                Line::from(vec![
+                    Span::raw(format!("{}: ", issue.severity())).style(Style::new().with_fg(color)),
+                    Span::raw(issue.to_string()),
+                ])
+            });
+            let prompt = [
+                Line::from(""),
+                Line::from(vec![
+                    key("Y"),
+                    Span::raw(" - Save anyway  "),
+                    key("N"),
+                    Span::raw(" - Keep editing"),
+                ]),
+            ];
+            Dialog::new(
+                Line::from("This save looks off").bold(),
+                issue_lines.chain(prompt),
+            )
+            .render(area, buf);
+        }
+
+        if let Mode::ConfirmingRunningGame { .. } = self.mode {
+            Dialog::running_game("this save", "Save anyway", "Keep editing").render(area, buf);
+        }
+
+        if self.mode == Mode::ConfirmingExit {
+            Dialog::new(
+                Line::from("Unsaved changes").bold(),
+                [
+                    Line::from("Save changes before exiting?"),
+                    Line::from(""),
+                    Line::from(vec![
+                        key("Y"),
+                        Span::raw(" - Save and exit  "),
+                        key("N"),
+                        Span::raw(" - Exit without saving  "),
+                        key("Esc"),
+                        Span::raw(" - Keep editing"),
+                    ]),
+                ],
+            )
+            .render(area, buf);
+        }
+
+        if let Some(conflict) = self.conflicts.first() {
+            draw_conflict(conflict, self.pending_save.is_some(), area, buf);
+        }
+    }
+}
+
+fn draw_conflict(conflict: &Conflict, is_save_pending: bool, area: Rect, buf: &mut Buffer) {
+    let key = |text| Span::raw(text).style(Style::new().with_fg(IndexedColor::DarkYellow));
+    let row = |changed_by: &str, path: &str, yours: &str, disk: &str| {
+        Line::from(format!("{changed_by:<8}{path:<36}{yours:<26}{disk:<26}"))
+    };
+
+    let mut lines = vec![
+        Line::from("It was written by the game while you had unsaved changes in it"),
+        Line::from(""),
+        row("Changed", "Field", "Yours", "On disk").bold(),
+    ];
+    lines.extend(
+        conflict
+            .differences
+            .iter()
+            .take(App::MAX_CONFLICT_ROWS)
+            .map(|difference| {
+                let line = row(
+                    &difference.changed_by.to_string(),
+                    &difference.path,
+                    &difference.yours_text(),
+                    &difference.disk_text(),
+                );
+                match difference.changed_by {
+                    ChangedBy::Both => line.style(Style::new().with_fg(IndexedColor::BrightRed)),
+                    ChangedBy::You | ChangedBy::Disk => line,
+                }
+            }),
+    );
+    if let Some(n_more) = conflict
+        .differences
+        .len()
+        .checked_sub(App::MAX_CONFLICT_ROWS)
+        .filter(|&n_more| n_more > 0)
+    {
+        lines.push(Line::from(format!("...and {n_more} more")));
+    }
+
+    let mut prompt = Vec::new();
+    if conflict.can_merge() {
+        prompt.extend([
+            key("M"),
+            Span::raw(" - Merge, yours win where both changed  "),
+        ]);
+    }
+    prompt.extend([
+        key("K"),
+        Span::raw(" - Keep yours  "),
+        key("D"),
+        Span::raw(" - Take the one on disk"),
+    ]);
+    lines.push(Line::from(""));
+    if is_save_pending {
+        lines.push(Line::from("Your save waits until this is resolved"));
+    }
+    lines.push(Line::from(prompt));
+
+    Dialog::new(
+        Line::from(format!("`{}` changed on disk", conflict.file.file_name())).bold(),
+        lines,
+    )
+    .render(area, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use binary_file::SaveFormat;
+    use online_profile::{PlayerOnlineProfile, Visibility};
+    use ratatui::crossterm::event::{Event as RatatuiEvent, KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::{
+        savefile::{self, Savefile, temp_saves::TempSaves},
+        tui::{Event, HandleEvent},
+    };
+
+    use super::App;
+
+    fn press(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+        let key = RatatuiEvent::Key(KeyEvent::new(code, modifiers));
+        let event = Event::empty(Instant::now()).follow_with(&key, Instant::now());
+        app.handle_event(&event);
+    }
+
+    #[rstest::rstest]
+    fn save_waits_for_conflict_on_the_same_tick() {
+        let saves = TempSaves::new();
+        let env = saves.env();
+        savefile::create_missing(&env).expect("Precondition: must create files");
+        let savefile = Savefile::load(env.clone()).expect("Precondition: must load");
+        let nickname = "edited".to_owned().try_into().expect("Precondition: valid");
+        savefile.profile().modify_nickname().send(nickname);
+        let mut app = App::new(savefile);
+
+        press(
+            &mut app,
+            KeyCode::Char(App::SAVE_KEY),
+            KeyModifiers::CONTROL,
+        );
+        let mut profile = PlayerOnlineProfile::default();
+        profile.country = Visibility::Show;
+        profile.save(&env).expect("Precondition: must write");
+        app.handle_savefile_updates().expect("Must sync");
+        assert_eq!(
+            Visibility::Show,
+            PlayerOnlineProfile::load(&env).expect("Must load").country,
+            "Must not overwrite the conflicting file"
+        );
+
+        press(&mut app, KeyCode::Char('k'), KeyModifiers::NONE);
+        app.handle_savefile_updates().expect("Must save");
+
+        let saved = PlayerOnlineProfile::load(&env).expect("Must load");
+        assert_eq!("edited", saved.nickname.to_string());
     }
 }
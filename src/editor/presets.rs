@@ -0,0 +1,246 @@
+use online_profile::PlayerOnlineProfile;
+use player_progress::PlayerProgress;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{List, Paragraph, Widget, Wrap},
+};
+
+use crate::{
+    collection::HoveringIndex,
+    editor::content_window::InteratibleTabComponent,
+    savefile::{
+        FileKind, Savefile,
+        preset::{Preset, Presets},
+        profile, progress,
+    },
+    style::{self, IndexedColor, WithColor},
+    tui::{Event, HandleEvent, VisualComponent},
+    widget::split,
+};
+
+/// Picks a preset from the editor folder and puts it on top of current files.
+///
+/// Presets are re-read on every apply, so editing them by hand doesn't need a restart.
+pub struct Tab {
+    presets: Presets,
+    progress: progress::Modify<PlayerProgress>,
+    profile: profile::Modify<PlayerOnlineProfile>,
+    names: Vec<String>,
+    current_index: usize,
+    /// The selected preset as JSON, read again only when the selection changes.
+    preview: String,
+    status: Status,
+}
+
+enum Status {
+    Idle,
+    Applied(String),
+    Failed(String),
+}
+
+struct InfoText<'a> {
+    presets: &'a Presets,
+}
+
+impl Tab {
+    pub const RELOAD_KEY: KeyCode = KeyCode::F(5);
+
+    #[must_use]
+    pub fn new(savefile: &Savefile) -> Self {
+        let mut tab = Self {
+            presets: savefile.presets(),
+            progress: savefile.progress().modify_whole(),
+            profile: savefile.profile().modify_whole(),
+            names: Vec::new(),
+            current_index: 0,
+            preview: String::new(),
+            status: Status::Idle,
+        };
+        tab.reload();
+        tab
+    }
+
+    fn reload(&mut self) {
+        match self.presets.list() {
+            Ok(names) => {
+                self.names = names;
+                self.status = Status::Idle;
+            }
+            Err(error) => {
+                self.names = Vec::new();
+                self.status = Status::Failed(error.to_string());
+            }
+        }
+        self.current_index = self.current_index.min(self.names.len().saturating_sub(1));
+        self.load_preview();
+    }
+
+    /// Shows the file as is, it's the most honest summary of what changes.
+    fn load_preview(&mut self) {
+        self.preview = self
+            .names
+            .get(self.current_index)
+            .and_then(|name| self.presets.load(name).ok())
+            .and_then(|preset| serde_json::to_string_pretty(&preset).ok())
+            .unwrap_or_default();
+    }
+
+    fn apply_current(&mut self) {
+        let Some(name) = self.names.get(self.current_index).cloned() else {
+            return;
+        };
+        match self.presets.load(&name) {
+            Ok(preset) => {
+                self.apply(&preset);
+                self.status = Status::Applied(name);
+            }
+            Err(error) => self.status = Status::Failed(error.to_string()),
+        }
+    }
+
+    fn apply(&mut self, preset: &Preset) {
+        if preset.has_progress_fields() {
+            let mut progress = self.progress.get();
+            preset.apply_to_progress(&mut progress);
+            self.progress.send(progress);
+        }
+        if preset.has_profile_fields() {
+            let mut profile = self.profile.get();
+            preset.apply_to_profile(&mut profile);
+            self.profile.send(profile);
+        }
+    }
+
+    fn render_list(&self, area: Rect, buf: &mut Buffer) {
+        let [list_area, status_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(2)]).areas(area);
+
+        if self.names.is_empty() {
+            Paragraph::new("No presets yet, make one with `aos2-save-editor preset save <name>`")
+                .wrap(Wrap { trim: true })
+                .render(list_area, buf);
+        } else {
+            let lines = self.names.iter().enumerate().map(|(index, name)| {
+                let is_selected = index == self.current_index;
+                Line::from(name.as_str()).style(style::Selection::from_is_selected(is_selected))
+            });
+            List::new(lines).render(list_area, buf);
+        }
+
+        let status = match &self.status {
+            Status::Idle => Line::default(),
+            Status::Applied(name) => Line::from(format!("Applied `{name}`, save to keep it"))
+                .style(Style::new().with_fg(IndexedColor::DarkGreen)),
+            Status::Failed(error) => {
+                Line::from(error.as_str()).style(Style::new().with_fg(IndexedColor::BrightRed))
+            }
+        };
+        Paragraph::new(status)
+            .wrap(Wrap { trim: true })
+            .render(status_area, buf);
+    }
+
+    fn render_preview(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.preview.as_str())
+            .style(Style::new().with_fg(IndexedColor::Gray))
+            .render(area, buf);
+    }
+}
+
+impl HandleEvent for Tab {
+    fn handle_event(&mut self, event: &Event) {
+        let hover = HoveringIndex::from_collection(&self.names).with_current(self.current_index);
+        let previous_index = self.current_index;
+        match event.key_code() {
+            Some(KeyCode::Up) => {
+                self.current_index = hover.previous().into_index().unwrap_or_default();
+            }
+            Some(KeyCode::Down) => {
+                self.current_index = hover.next().into_index().unwrap_or_default();
+            }
+            Some(KeyCode::Home) => {
+                self.current_index = hover.first().into_index().unwrap_or_default();
+            }
+            Some(KeyCode::End) => {
+                self.current_index = hover.last().into_index().unwrap_or_default();
+            }
+            Some(KeyCode::Enter) => self.apply_current(),
+            Some(Self::RELOAD_KEY) => self.reload(),
+            _ => (),
+        }
+        if self.current_index != previous_index {
+            self.load_preview();
+        }
+    }
+}
+
+impl VisualComponent for Tab {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let top = split::Area {
+            constraint: InfoText::CONSTRAINT,
+            render: |area: Rect, buf: &mut Buffer| {
+                InfoText {
+                    presets: &self.presets,
+                }
+                .render(area, buf);
+            },
+        };
+        let bottom = split::Area {
+            constraint: Constraint::Fill(1),
+            render: |area: Rect, buf: &mut Buffer| {
+                let left = split::Area {
+                    constraint: Constraint::Fill(1),
+                    render: |area: Rect, buf: &mut Buffer| self.render_list(area, buf),
+                };
+                let right = split::Area {
+                    constraint: Constraint::Fill(1),
+                    render: |area: Rect, buf: &mut Buffer| self.render_preview(area, buf),
+                };
+                split::Vertical { left, right }.render(area, buf);
+            },
+        };
+        split::Horizontal { top, bottom }.render(area, buf);
+    }
+}
+
+impl InteratibleTabComponent for Tab {
+    fn name(&self) -> &'static str {
+        "Presets"
+    }
+
+    fn files(&self) -> &'static [FileKind] {
+        &[FileKind::Progress, FileKind::Profile]
+    }
+}
+
+impl InfoText<'_> {
+    const N_LINES: u16 = 3;
+    const CONSTRAINT: Constraint = Constraint::Length(Self::N_LINES);
+}
+
+impl Widget for InfoText<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let key = |text| Span::raw(text).style(Style::new().with_fg(IndexedColor::DarkYellow));
+        let lines = [
+            Line::from("Presets only change the fields they have, the rest stays")
+                .style(Style::new().with_bg(Color::Black).with_fg(Color::White))
+                .centered(),
+            Line::from(vec![
+                key("Enter"),
+                Span::raw(" - apply  "),
+                key("F5"),
+                Span::raw(" - reload list"),
+            ])
+            .centered(),
+            Line::from(format!("Stored in {}", self.presets.folder().display())).centered(),
+        ];
+        List::new(lines).render(area, buf);
+    }
+}
@@ -8,7 +8,7 @@ use ratatui::{
 
 use crate::{
     editor::content_window::InteratibleTabComponent,
-    savefile::Savefile,
+    savefile::{FileKind, Savefile},
     style::{IndexedColor, WithColor},
     tui::{Event, HandleEvent, VisualComponent},
     widget::split,
@@ -18,12 +18,15 @@ use super::table::{self, Table};
 
 pub struct Tab {
     tables: table::Collection<2>,
+    info: InfoText,
 }
 
-struct InfoText;
+struct InfoText {
+    version: String,
+}
 
 impl InfoText {
-    const N_LINES: u16 = 3;
+    const N_LINES: u16 = 4;
     const CONSTRAINT: Constraint = Constraint::Length(Self::N_LINES);
 }
 
@@ -38,8 +41,22 @@ impl Tab {
             Box::new(table::Generic::new("Background", background)),
         ];
 
+        let profile = savefile.profile();
+        let layout = match profile.known_layout() {
+            Some(known) => known.to_string(),
+            None => "unknown layout".to_owned(),
+        };
+        let version = match profile.version().known() {
+            Some(known) if profile.is_up_to_date() => format!("Profile from game {known}"),
+            Some(known) => {
+                format!("Profile from game {known}, {layout}, run `migrate` to update it")
+            }
+            None => format!("Profile version {}, {layout}", profile.version()),
+        };
+
         Self {
             tables: table::Collection::new(tables),
+            info: InfoText { version },
         }
     }
 }
@@ -55,7 +72,7 @@ impl VisualComponent for Tab {
         let top = split::Area {
             constraint: InfoText::CONSTRAINT,
             render: |area: Rect, buf: &mut Buffer| {
-                InfoText.render(area, buf);
+                self.info.render(area, buf);
             },
         };
         let bottom = split::Area {
@@ -72,6 +89,10 @@ impl InteratibleTabComponent for Tab {
     fn name(&self) -> &'static str {
         "Online Avatar"
     }
+
+    fn files(&self) -> &'static [FileKind] {
+        &[FileKind::Profile]
+    }
 }
 
 impl VisualComponent for InfoText {
@@ -88,6 +109,7 @@ impl VisualComponent for InfoText {
                         .with_fg(IndexedColor::DarkYellow),
                 )
                 .centered(),
+            Line::from(self.version.as_str()).centered(),
         ];
         List::new(lines).render(area, buf);
     }
@@ -0,0 +1,230 @@
+use std::fmt::Display;
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{List, Paragraph, Widget},
+};
+
+use crate::{
+    collection::SelectableArray,
+    editor::content_window::InteratibleTabComponent,
+    savefile::{FileKind, Savefile, profile},
+    style::{self, IndexedColor, WithColor},
+    tui::{Event, HandleEvent, VisualComponent},
+    widget::{split, text_input::TextInput},
+};
+
+pub struct Tab {
+    fields: SelectableArray<Box<dyn Field>, 3>,
+}
+
+trait Field: HandleEvent + Send {
+    fn render(&self, area: Rect, buf: &mut Buffer, is_active: bool);
+}
+
+/// Edits the profile on every key press, as long as the text is valid.
+///
+/// Invalid text stays in the input until fixed, with the reason below it.
+struct TextField<T> {
+    name: &'static str,
+    data: profile::Modify<T>,
+    input: TextInput,
+    error: Option<String>,
+    secrecy: Secrecy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Secrecy {
+    Public,
+    Hidden,
+    Revealed,
+}
+
+struct InfoText;
+
+impl InfoText {
+    const N_LINES: u16 = 3;
+    const CONSTRAINT: Constraint = Constraint::Length(Self::N_LINES);
+}
+
+impl Tab {
+    pub const REVEAL_KEY: KeyCode = KeyCode::Tab;
+
+    #[must_use]
+    pub fn new(savefile: &Savefile) -> Self {
+        let profile = savefile.profile();
+        let fields: [Box<dyn Field>; 3] = [
+            Box::new(TextField::new(
+                "Nickname",
+                profile.modify_nickname(),
+                Secrecy::Public,
+            )),
+            Box::new(TextField::new(
+                "Lobby Name",
+                profile.modify_lobby_name(),
+                Secrecy::Public,
+            )),
+            Box::new(TextField::new(
+                "Lobby Password",
+                profile.modify_lobby_password(),
+                Secrecy::Hidden,
+            )),
+        ];
+        Self {
+            fields: SelectableArray::new(fields),
+        }
+    }
+}
+
+impl<T> TextField<T>
+where
+    T: Display,
+{
+    fn new(name: &'static str, data: profile::Modify<T>, secrecy: Secrecy) -> Self {
+        Self {
+            name,
+            input: TextInput::new(data.get().to_string()),
+            data,
+            error: None,
+            secrecy,
+        }
+    }
+}
+
+impl<T> HandleEvent for TextField<T>
+where
+    T: Display + TryFrom<String>,
+    T::Error: Display,
+{
+    fn handle_event(&mut self, event: &Event) {
+        // Undo may have changed the value behind our back.
+        if self.error.is_none() {
+            self.input.set_text(self.data.get().to_string());
+        }
+
+        match event.key_code() {
+            Some(Tab::REVEAL_KEY) => self.secrecy = self.secrecy.toggle(),
+            Some(KeyCode::Char(_)) if event.ctrl_char().is_some() => (),
+            Some(key) if self.input.handle_key(key) => {
+                match T::try_from(self.input.text().to_owned()) {
+                    Ok(value) => {
+                        self.error = None;
+                        self.data.send(value);
+                    }
+                    Err(error) => self.error = Some(error.to_string()),
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+impl<T> Field for TextField<T>
+where
+    T: Display + TryFrom<String>,
+    T::Error: Display,
+{
+    fn render(&self, area: Rect, buf: &mut Buffer, is_active: bool) {
+        let [name_area, input_area, error_area] =
+            Layout::vertical([Constraint::Length(1); 3]).areas(area);
+
+        let name = match self.secrecy {
+            Secrecy::Public => self.name.to_owned(),
+            Secrecy::Hidden => format!("{} (Tab to show)", self.name),
+            Secrecy::Revealed => format!("{} (Tab to hide)", self.name),
+        };
+        Paragraph::new(name)
+            .style(style::Selection::from_is_selected(is_active))
+            .render(name_area, buf);
+
+        // Show the saved value unless there's something invalid being typed.
+        let mut shown = self.input.clone();
+        if self.error.is_none() {
+            shown.set_text(self.data.get().to_string());
+        }
+        shown
+            .widget(is_active)
+            .masked(self.secrecy == Secrecy::Hidden)
+            .render(input_area, buf);
+
+        if let Some(error) = &self.error {
+            Line::from(error.as_str())
+                .style(Style::new().with_fg(IndexedColor::BrightRed))
+                .render(error_area, buf);
+        }
+    }
+}
+
+impl Secrecy {
+    fn toggle(self) -> Self {
+        match self {
+            Secrecy::Public => Secrecy::Public,
+            Secrecy::Hidden => Secrecy::Revealed,
+            Secrecy::Revealed => Secrecy::Hidden,
+        }
+    }
+}
+
+impl HandleEvent for Tab {
+    fn handle_event(&mut self, event: &Event) {
+        match event.key_code() {
+            Some(KeyCode::Up) => self.fields.select_previous(),
+            Some(KeyCode::Down | KeyCode::Enter) => self.fields.select_next(),
+            _ => self.fields.mut_current().handle_event(event),
+        }
+    }
+}
+
+impl VisualComponent for Tab {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let top = split::Area {
+            constraint: InfoText::CONSTRAINT,
+            render: |area: Rect, buf: &mut Buffer| {
+                InfoText.render(area, buf);
+            },
+        };
+        let bottom = split::Area {
+            constraint: Constraint::Fill(1),
+            render: |area: Rect, buf: &mut Buffer| {
+                // Name, input, error and a gap.
+                let constraints = self.fields.iter().map(|_| Constraint::Length(4));
+                let areas = Layout::vertical(constraints).split(area);
+                for (index, (field, &area)) in self.fields.iter().zip(areas.iter()).enumerate() {
+                    let is_active = index == self.fields.current_index();
+                    field.render(area, buf, is_active);
+                }
+            },
+        };
+        split::Horizontal { top, bottom }.render(area, buf);
+    }
+}
+
+impl InteratibleTabComponent for Tab {
+    fn name(&self) -> &'static str {
+        "Lobby"
+    }
+
+    fn files(&self) -> &'static [FileKind] {
+        &[FileKind::Profile]
+    }
+}
+
+impl Widget for InfoText {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let lines: [Line<'_>; Self::N_LINES as usize] = [
+            Line::from("Nickname and the lobby you host online")
+                .style(Style::new().with_bg(Color::Black).with_fg(Color::White))
+                .centered(),
+            Line::from("Only ASCII characters, changes apply as you type").centered(),
+            Line::from("Up / Down to switch fields").centered(),
+        ];
+        List::new(lines).render(area, buf);
+    }
+}
@@ -1,5 +1,8 @@
 pub mod avatar;
+pub mod lobby;
+pub mod options;
 pub mod title;
+pub mod unlocks;
 
 mod table;
 mod widget;
@@ -0,0 +1,146 @@
+use online_profile::Visibility;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::{Constraint, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{List, Widget},
+};
+
+use crate::{
+    collection::SelectableArray,
+    editor::content_window::InteratibleTabComponent,
+    savefile::{FileKind, Savefile, profile},
+    style::{self, IndexedColor, WithColor},
+    tui::{Event, HandleEvent, VisualComponent},
+    widget::split,
+};
+
+/// Settings the game scatters across its online menus.
+pub struct Tab {
+    options: SelectableArray<Toggle, 4>,
+}
+
+struct Toggle {
+    name: &'static str,
+    description: &'static str,
+    data: profile::Modify<Visibility>,
+}
+
+struct InfoText;
+
+impl InfoText {
+    const N_LINES: u16 = 3;
+    const CONSTRAINT: Constraint = Constraint::Length(Self::N_LINES);
+}
+
+impl Tab {
+    #[must_use]
+    pub fn new(savefile: &Savefile) -> Self {
+        let profile = savefile.profile();
+        let options = [
+            Toggle {
+                name: "Country",
+                description: "your flag next to the nickname",
+                data: profile.modify_country(),
+            },
+            Toggle {
+                name: "In-game Title",
+                description: "your title during matches",
+                data: profile.modify_ingame_title(),
+            },
+            Toggle {
+                name: "Hitstun Meter",
+                description: "combo and hitstun info during matches",
+                data: profile.modify_hitstun_meter(),
+            },
+            Toggle {
+                name: "Spectators",
+                description: "others can watch your matches",
+                data: profile.modify_spectators(),
+            },
+        ];
+        Self {
+            options: SelectableArray::new(options),
+        }
+    }
+}
+
+impl Toggle {
+    fn line(&self, is_selected: bool) -> Line<'_> {
+        let (value, color) = match self.data.get() {
+            Visibility::Show => ("Show", IndexedColor::DarkGreen),
+            Visibility::Hide => ("Hide", IndexedColor::BrightRed),
+        };
+        Line::from(vec![
+            Span::raw(format!(" {:<16}", self.name)),
+            Span::raw(format!("{value:<6}")).style(Style::new().with_fg(color)),
+            Span::raw(self.description),
+        ])
+        .style(style::Selection::from_is_selected(is_selected))
+    }
+}
+
+impl HandleEvent for Tab {
+    fn handle_event(&mut self, event: &Event) {
+        match event.key_code() {
+            Some(KeyCode::Up) => self.options.select_previous(),
+            Some(KeyCode::Down) => self.options.select_next(),
+            Some(KeyCode::Enter | KeyCode::Char(' ')) => {
+                let data = &mut self.options.mut_current().data;
+                data.send(data.get().toggle());
+            }
+            _ => (),
+        }
+    }
+}
+
+impl VisualComponent for Tab {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let top = split::Area {
+            constraint: InfoText::CONSTRAINT,
+            render: |area: Rect, buf: &mut Buffer| {
+                InfoText.render(area, buf);
+            },
+        };
+        let bottom = split::Area {
+            constraint: Constraint::Fill(1),
+            render: |area: Rect, buf: &mut Buffer| {
+                let lines = self
+                    .options
+                    .iter()
+                    .enumerate()
+                    .map(|(index, option)| option.line(index == self.options.current_index()));
+                List::new(lines).render(area, buf);
+            },
+        };
+        split::Horizontal { top, bottom }.render(area, buf);
+    }
+}
+
+impl InteratibleTabComponent for Tab {
+    fn name(&self) -> &'static str {
+        "Online Options"
+    }
+
+    fn files(&self) -> &'static [FileKind] {
+        &[FileKind::Profile]
+    }
+}
+
+impl Widget for InfoText {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let lines: [Line<'_>; Self::N_LINES as usize] = [
+            Line::from("What other players see of you online")
+                .style(Style::new().with_bg(Color::Black).with_fg(Color::White))
+                .centered(),
+            Line::from("The game only changes these in different menus").centered(),
+            Line::from("Enter or Space to toggle").centered(),
+        ];
+        List::new(lines).render(area, buf);
+    }
+}
@@ -8,7 +8,7 @@ use ratatui::{
 
 use crate::{
     editor::content_window::InteratibleTabComponent,
-    savefile::Savefile,
+    savefile::{FileKind, Savefile},
     style::{IndexedColor, WithColor},
     tui::{Event, HandleEvent, VisualComponent},
     widget::split,
@@ -74,6 +74,10 @@ impl InteratibleTabComponent for Tab {
     fn name(&self) -> &'static str {
         "Online Title"
     }
+
+    fn files(&self) -> &'static [FileKind] {
+        &[FileKind::Profile]
+    }
 }
 
 impl VisualComponent for InfoText {
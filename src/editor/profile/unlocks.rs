@@ -0,0 +1,340 @@
+use online_profile::{
+    avatar, title,
+    unlocks::{AvatarsSection, BackgroundsSection, Status, TitlesSection},
+};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::{Constraint, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{List, Widget},
+};
+
+use crate::{
+    collection::{HoveringIndex, SelectableArray},
+    editor::{
+        content_window::InteratibleTabComponent,
+        progress::widget::{TogglesContent, TogglesTable},
+    },
+    savefile::{FileKind, Savefile, profile},
+    style::{IndexedColor, WithColor},
+    tui::{Event, HandleEvent, VisualComponent},
+    widget::{sequence, split},
+};
+
+/// Online profile unlocks, one item at a time or many at once.
+///
+/// Typing filters all lists by name, and bulk actions only touch what's shown.
+pub struct Tab {
+    tables: SelectableArray<Box<dyn Table>, 3>,
+    filter: String,
+}
+
+trait Table: Send {
+    fn handle_event(&mut self, event: &Event, filter: &str);
+    /// Back to the first shown item, for when the filter changes.
+    fn reset_position(&mut self);
+    fn as_widget(&self, is_active: bool, filter: &str) -> TogglesTable<'_>;
+}
+
+trait Section: Clone + Send + AsRef<[Status]> + AsMut<[Status]> {
+    /// What the item is called in game, if known.
+    fn item_name(index: usize) -> Option<String>;
+}
+
+struct SectionTable<T> {
+    name: &'static str,
+    section: profile::Modify<T>,
+    /// Position among the shown items.
+    current_index: usize,
+}
+
+struct InfoText<'a> {
+    filter: &'a str,
+}
+
+impl Tab {
+    pub const OPEN_SHOWN_KEY: KeyCode = KeyCode::F(5);
+    pub const LOCK_SHOWN_KEY: KeyCode = KeyCode::F(6);
+
+    #[must_use]
+    pub fn new(savefile: &Savefile) -> Self {
+        let profile = savefile.profile();
+        let tables: [Box<dyn Table>; 3] = [
+            Box::new(SectionTable::new(
+                "Avatars",
+                profile.modify_unlockable_avatars(),
+            )),
+            Box::new(SectionTable::new(
+                "Backgrounds",
+                profile.modify_unlockable_backgrounds(),
+            )),
+            Box::new(SectionTable::new("Titles", profile.modify_titles())),
+        ];
+        Self {
+            tables: SelectableArray::new(tables),
+            filter: String::new(),
+        }
+    }
+
+    /// Positions are among the shown items, so a new filter makes them meaningless.
+    fn reset_positions(&mut self) {
+        for table in self.tables.iter_mut() {
+            table.reset_position();
+        }
+    }
+}
+
+impl<T: Section> SectionTable<T> {
+    fn new(name: &'static str, section: profile::Modify<T>) -> Self {
+        Self {
+            name,
+            section,
+            current_index: 0,
+        }
+    }
+
+    /// Indexes in the section with their names, matching the filter.
+    fn shown(filter: &str, section: &T) -> Vec<(usize, String)> {
+        let filter = filter.to_lowercase();
+        (0..section.as_ref().len())
+            .map(|index| {
+                let name = T::item_name(index).unwrap_or_else(|| format!("Unknown #{index}"));
+                (index, name)
+            })
+            .filter(|(_, name)| name.to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    fn set_shown(&mut self, filter: &str, status: Status) {
+        let mut section = self.section.get();
+        for (index, _) in Self::shown(filter, &section) {
+            section.as_mut()[index] = status;
+        }
+        self.section.send(section);
+    }
+}
+
+impl<T: Section> Table for SectionTable<T> {
+    fn handle_event(&mut self, event: &Event, filter: &str) {
+        let mut section = self.section.get();
+        let shown = Self::shown(filter, &section);
+        let hover = HoveringIndex::from_collection(&shown).with_current(self.current_index);
+        match event.key_code() {
+            Some(KeyCode::Up) => {
+                self.current_index = hover.previous().into_index().unwrap_or_default();
+            }
+            Some(KeyCode::Down) => {
+                self.current_index = hover.next().into_index().unwrap_or_default();
+            }
+            Some(KeyCode::Home) => {
+                self.current_index = hover.first().into_index().unwrap_or_default();
+            }
+            Some(KeyCode::End) => {
+                self.current_index = hover.last().into_index().unwrap_or_default();
+            }
+            Some(KeyCode::Enter) => {
+                if let Some(&(index, _)) = hover.into_index().and_then(|i| shown.get(i)) {
+                    let status = &mut section.as_mut()[index];
+                    *status = !*status;
+                    self.section.send(section);
+                }
+            }
+            Some(Tab::OPEN_SHOWN_KEY) => self.set_shown(filter, Status::Open),
+            Some(Tab::LOCK_SHOWN_KEY) => self.set_shown(filter, Status::Locked),
+            _ => (),
+        }
+    }
+
+    fn reset_position(&mut self) {
+        self.current_index = 0;
+    }
+
+    fn as_widget(&self, is_active: bool, filter: &str) -> TogglesTable<'_> {
+        let section = self.section.get();
+        let shown = Self::shown(filter, &section);
+        let n_open = section
+            .as_ref()
+            .iter()
+            .filter(|status| status.is_open())
+            .count();
+        let statuses = section.as_ref();
+        TogglesTable {
+            name: format!("{} ({n_open}/{})", self.name, statuses.len()).into(),
+            content: TogglesContent::new(
+                shown
+                    .into_iter()
+                    .map(|(index, name)| (name, statuses[index])),
+            )
+            .with_current(self.current_index),
+            is_active,
+        }
+    }
+}
+
+impl HandleEvent for Tab {
+    fn handle_event(&mut self, event: &Event) {
+        match event.key_code() {
+            Some(KeyCode::Left) => self.tables.select_previous(),
+            Some(KeyCode::Right) => self.tables.select_next(),
+            Some(KeyCode::Backspace) => {
+                self.filter.pop();
+                self.reset_positions();
+            }
+            Some(KeyCode::Char(c)) if event.ctrl_char().is_none() => {
+                self.filter.push(c);
+                self.reset_positions();
+            }
+            _ => self.tables.mut_current().handle_event(event, &self.filter),
+        }
+    }
+}
+
+impl VisualComponent for Tab {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let top = split::Area {
+            constraint: InfoText::CONSTRAINT,
+            render: |area: Rect, buf: &mut Buffer| {
+                InfoText {
+                    filter: &self.filter,
+                }
+                .render(area, buf);
+            },
+        };
+        let bottom = split::Area {
+            constraint: Constraint::Fill(1),
+            render: |area: Rect, buf: &mut Buffer| {
+                sequence::VerticallySeparated {
+                    widgets: self.tables.iter().enumerate().map(|(index, table)| {
+                        let is_selected = index == self.tables.current_index();
+                        table.as_widget(is_selected, &self.filter)
+                    }),
+                }
+                .render(area, buf);
+            },
+        };
+        split::Horizontal { top, bottom }.render(area, buf);
+    }
+}
+
+impl InteratibleTabComponent for Tab {
+    fn name(&self) -> &'static str {
+        "Online Unlocks"
+    }
+
+    fn files(&self) -> &'static [FileKind] {
+        &[FileKind::Profile]
+    }
+}
+
+impl InfoText<'_> {
+    const N_LINES: u16 = 3;
+    const CONSTRAINT: Constraint = Constraint::Length(Self::N_LINES);
+}
+
+impl Widget for InfoText<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let key = |text| Span::raw(text).style(Style::new().with_fg(IndexedColor::DarkYellow));
+        let filter = if self.filter.is_empty() {
+            Line::from("Type to filter by name").centered()
+        } else {
+            Line::from(vec![
+                Span::raw("Filter: "),
+                Span::raw(self.filter)
+                    .style(Style::new().with_bg(Color::White).with_fg(Color::Black)),
+            ])
+            .centered()
+        };
+        let lines = [
+            Line::from("Avatars, backgrounds and titles you can pick online")
+                .style(Style::new().with_bg(Color::Black).with_fg(Color::White))
+                .centered(),
+            Line::from(vec![
+                key("Enter"),
+                Span::raw(" - toggle  "),
+                key("F5"),
+                Span::raw(" - unlock shown  "),
+                key("F6"),
+                Span::raw(" - lock shown"),
+            ])
+            .centered(),
+            filter,
+        ];
+        List::new(lines).render(area, buf);
+    }
+}
+
+impl Section for AvatarsSection {
+    fn item_name(index: usize) -> Option<String> {
+        let id = u32::try_from(index).ok()?;
+        avatar::Character::try_from(id)
+            .ok()
+            .map(|character| character.to_string())
+    }
+}
+
+impl Section for BackgroundsSection {
+    fn item_name(index: usize) -> Option<String> {
+        let id = u32::try_from(index).ok()?;
+        avatar::Background::try_from(id)
+            .ok()
+            .map(|background| background.to_string())
+    }
+}
+
+impl Section for TitlesSection {
+    fn item_name(index: usize) -> Option<String> {
+        let id = u32::try_from(index).ok()?;
+        title::Text::try_from(id).ok().map(|text| text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use ratatui::{
+        buffer::{Buffer, Cell},
+        crossterm::event::{Event as RatatuiEvent, KeyCode, KeyEvent, KeyModifiers},
+        layout::Rect,
+    };
+
+    use crate::{
+        savefile::{self, Savefile, temp_saves::TempSaves},
+        tui::{Event, HandleEvent, VisualComponent},
+    };
+
+    use super::Tab;
+
+    fn press(tab: &mut Tab, code: KeyCode) {
+        let key = RatatuiEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let event = Event::empty(Instant::now()).follow_with(&key, Instant::now());
+        tab.handle_event(&event);
+    }
+
+    fn rendered(tab: &Tab) -> String {
+        let area = Rect::new(0, 0, 160, 40);
+        let mut buf = Buffer::empty(area);
+        tab.render(area, &mut buf);
+        buf.content().iter().map(Cell::symbol).collect()
+    }
+
+    #[rstest::rstest]
+    fn filtered_rows_are_shown_after_scrolling() {
+        let saves = TempSaves::new();
+        savefile::create_missing(&saves.env()).expect("Precondition: must create saves");
+        let savefile = Savefile::load(saves.env()).expect("Precondition: must load");
+        let mut tab = Tab::new(&savefile);
+
+        press(&mut tab, KeyCode::End);
+        for c in "military".chars() {
+            press(&mut tab, KeyCode::Char(c));
+        }
+
+        assert!(rendered(&tab).contains("100% Sora Military"));
+    }
+}
@@ -1,5 +1,6 @@
+pub mod widget;
+
 mod tables;
-mod widget;
 
 use ratatui::{
     buffer::Buffer,
@@ -11,7 +12,7 @@ use ratatui::{
 
 use crate::{
     editor::content_window::InteratibleTabComponent,
-    savefile::Savefile,
+    savefile::{FileKind, Savefile},
     style::{IndexedColor, WithColor},
     tui::{Event, HandleEvent, VisualComponent},
     widget::split,
@@ -69,6 +70,10 @@ impl InteratibleTabComponent for Tab {
     fn name(&self) -> &'static str {
         "Progress"
     }
+
+    fn files(&self) -> &'static [FileKind] {
+        &[FileKind::Progress]
+    }
 }
 
 impl Widget for InfoText {
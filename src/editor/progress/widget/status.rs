@@ -21,6 +21,12 @@ impl From<player_progress::Status> for Status {
     }
 }
 
+impl From<online_profile::unlocks::Status> for Status {
+    fn from(status: online_profile::unlocks::Status) -> Self {
+        status.is_open().into()
+    }
+}
+
 impl From<Status> for Cell<'_> {
     fn from(Status(is_enabled): Status) -> Self {
         let style = if is_enabled {
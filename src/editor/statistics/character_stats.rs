@@ -1,6 +1,7 @@
-use player_progress::{Character, Run};
+use player_progress::{Character, PerfectArcadeMode, PerfectStoryMode, Run};
 use ratatui::{
     buffer::Buffer,
+    crossterm::event::KeyCode,
     layout::{Constraint, Rect},
     style::{Style, Stylize},
     text::Text,
@@ -9,12 +10,17 @@ use ratatui::{
 
 use crate::{
     savefile::progress,
-    style::{IndexedColor, WithColor},
-    tui::VisualComponent,
+    style::{IndexedColor, Selection, WithColor},
+    tui::{Event, HandleEvent},
 };
 
+/// 1CC stars of every character, toggled one cell at a time.
 pub struct CharacterStats {
     stats: progress::Read<progress::ComplationStats>,
+    arcade: [progress::Modify<PerfectArcadeMode>; 3],
+    story: progress::Modify<PerfectStoryMode>,
+    row: usize,
+    column: usize,
 }
 
 struct ContentRow {
@@ -31,8 +37,65 @@ struct RawRow<'a>([Cell<'a>; const { ContentRow::COLUMN_HEADERS.len() }]);
 struct CompletionStatus(Option<Run>);
 
 impl CharacterStats {
-    pub fn new(stats: progress::Read<progress::ComplationStats>) -> Self {
-        Self { stats }
+    const N_RUN_COLUMNS: usize = 4;
+
+    pub fn new(
+        stats: progress::Read<progress::ComplationStats>,
+        arcade: [progress::Modify<PerfectArcadeMode>; 3],
+        story: progress::Modify<PerfectStoryMode>,
+    ) -> Self {
+        Self {
+            stats,
+            arcade,
+            story,
+            row: 0,
+            column: 0,
+        }
+    }
+
+    /// Left from here leaves the table.
+    pub fn is_at_first_column(&self) -> bool {
+        self.column == 0
+    }
+
+    fn toggle_current(&mut self) {
+        let Self {
+            arcade,
+            story,
+            row,
+            column,
+            ..
+        } = self;
+        if let Some(runs) = arcade.get_mut(*column) {
+            let mut array = runs.get().to_array();
+            array[*row] = !array[*row];
+            runs.send(array.into());
+        } else {
+            let mut array = story.get().to_array();
+            // Sumika has no story mode.
+            if let Some(run) = array.get_mut(*row) {
+                *run = !*run;
+                story.send(array.into());
+            }
+        }
+    }
+}
+
+impl HandleEvent for CharacterStats {
+    fn handle_event(&mut self, event: &Event) {
+        let last_row = PerfectArcadeMode::N_CHARACTERS - 1;
+        match event.key_code() {
+            Some(KeyCode::Up) => self.row = self.row.saturating_sub(1),
+            Some(KeyCode::Down) => self.row = (self.row + 1).min(last_row),
+            Some(KeyCode::Home) => self.row = 0,
+            Some(KeyCode::End) => self.row = last_row,
+            Some(KeyCode::Left) => self.column = self.column.saturating_sub(1),
+            Some(KeyCode::Right) => {
+                self.column = (self.column + 1).min(Self::N_RUN_COLUMNS - 1);
+            }
+            Some(KeyCode::Enter) => self.toggle_current(),
+            _ => (),
+        }
     }
 }
 
@@ -104,8 +167,8 @@ impl<'a> From<RawRow<'a>> for Row<'a> {
     }
 }
 
-impl VisualComponent for CharacterStats {
-    fn render(&self, area: Rect, buf: &mut Buffer) {
+impl CharacterStats {
+    pub fn render(&self, area: Rect, buf: &mut Buffer, is_active: bool) {
         let progress::ComplationStats {
             arcade_easy,
             arcade_medium,
@@ -134,7 +197,15 @@ impl VisualComponent for CharacterStats {
                     story,
                 },
             )
-            .map(Row::from);
+            .map(RawRow::from)
+            .enumerate()
+            .map(|(row, RawRow(mut cells))| {
+                if is_active && row == self.row {
+                    let cell = &mut cells[self.column + 1];
+                    *cell = std::mem::take(cell).style(Selection::Selected);
+                }
+                Row::from(RawRow(cells))
+            });
 
         let rows = std::iter::once(ContentRow::title())
             .chain(content)
@@ -1,43 +1,171 @@
-use player_progress::SingleplayerWins;
+use player_progress::{CounterMismatch, OneCreditCounter, SingleplayerWins};
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
-    text::Text,
-    widgets::{List, Widget},
+    crossterm::event::KeyCode,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Paragraph, Widget, Wrap},
 };
 
-use crate::{savefile::progress, tui::VisualComponent};
+use crate::{
+    savefile::progress,
+    style::{IndexedColor, WithColor},
+    tui::{Event, HandleEvent},
+    widget::number_input::NumberInput,
+};
 
 pub struct SingleplayerMatchStats {
-    wins: progress::Read<SingleplayerWins>,
+    wins: progress::Modify<SingleplayerWins>,
+    mismatches: progress::Read<Vec<CounterMismatch>>,
+    current: Counter,
+    input: NumberInput,
+    error: Option<String>,
+}
+
+/// The 1CC counters, then the total.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Counter {
+    OneCredit(OneCreditCounter),
+    Total,
 }
 
 impl SingleplayerMatchStats {
-    pub fn new(wins: progress::Read<SingleplayerWins>) -> Self {
-        Self { wins }
+    pub fn new(
+        wins: progress::Modify<SingleplayerWins>,
+        mismatches: progress::Read<Vec<CounterMismatch>>,
+    ) -> Self {
+        let current = Counter::OneCredit(OneCreditCounter::ArcadeEasy);
+        Self {
+            input: NumberInput::new(*current.of(&mut wins.get())),
+            wins,
+            mismatches,
+            current,
+            error: None,
+        }
+    }
+
+    /// Whether keys like `Right` still have something to do in the input.
+    pub fn is_editing(&self) -> bool {
+        self.error.is_some() || !self.input.is_cursor_at_end()
+    }
+
+    fn select(&mut self, counter: Counter) {
+        self.current = counter;
+        self.error = None;
+        self.input.set_value(*counter.of(&mut self.wins.get()));
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer, is_active: bool) {
+        let mut wins = self.wins.get();
+
+        // Label, value and a gap for each counter.
+        let counters = Counter::all();
+        let constraints = counters
+            .iter()
+            .flat_map(|_| [Constraint::Length(1); 3])
+            .chain(std::iter::once(Constraint::Fill(1)));
+        let areas = Layout::vertical(constraints).split(area);
+
+        for (counter, areas) in counters.into_iter().zip(areas.chunks(3)) {
+            let &[label_area, value_area, gap_area] = areas else {
+                continue;
+            };
+            Line::from(format!("{counter}:")).render(label_area, buf);
+
+            let value_area = Rect {
+                x: value_area.x + 4,
+                width: value_area.width.saturating_sub(4),
+                ..value_area
+            };
+            if counter == self.current && is_active {
+                // Show the saved value unless there's something invalid being typed.
+                let mut shown = self.input.clone();
+                if self.error.is_none() {
+                    shown.set_value(*counter.of(&mut wins));
+                }
+                shown.widget(true).render(value_area, buf);
+            } else {
+                Line::from(counter.of(&mut wins).to_string()).render(value_area, buf);
+            }
+
+            if counter == self.current
+                && let Some(error) = &self.error
+            {
+                Line::from(error.as_str())
+                    .style(Style::new().with_fg(IndexedColor::BrightRed))
+                    .render(gap_area, buf);
+            }
+        }
+
+        if let Some(&warnings_area) = areas.last() {
+            let warnings = self.mismatches.get().into_iter().map(|mismatch| {
+                Line::from(format!("! {mismatch}"))
+                    .style(Style::new().with_fg(IndexedColor::DarkYellow))
+            });
+            Paragraph::new(warnings.collect::<Vec<_>>())
+                .wrap(Wrap { trim: true })
+                .render(warnings_area, buf);
+        }
+    }
+}
+
+impl HandleEvent for SingleplayerMatchStats {
+    fn handle_event(&mut self, event: &Event) {
+        // Undo may have changed the value behind our back.
+        if self.error.is_none() {
+            self.input.set_value(*self.current.of(&mut self.wins.get()));
+        }
+
+        let all = Counter::all();
+        let index = all
+            .iter()
+            .position(|&counter| counter == self.current)
+            .unwrap_or_default();
+        match event.key_code() {
+            Some(KeyCode::Up) => {
+                self.select(all[index.saturating_sub(1)]);
+            }
+            Some(KeyCode::Down) => {
+                self.select(all[(index + 1).min(all.len() - 1)]);
+            }
+            Some(KeyCode::Char(_)) if event.ctrl_char().is_some() => (),
+            Some(key) if self.input.handle_key(key) => match self.input.value() {
+                Ok(value) => {
+                    self.error = None;
+                    let mut wins = self.wins.get();
+                    *self.current.of(&mut wins) = value;
+                    self.wins.send(wins);
+                }
+                Err(error) => self.error = Some(error.to_string()),
+            },
+            _ => (),
+        }
+    }
+}
+
+impl Counter {
+    fn all() -> Vec<Self> {
+        OneCreditCounter::members()
+            .into_iter()
+            .map(Counter::OneCredit)
+            .chain(std::iter::once(Counter::Total))
+            .collect()
+    }
+
+    fn of(self, wins: &mut SingleplayerWins) -> &mut u32 {
+        match self {
+            Counter::OneCredit(counter) => wins.one_credit_mut(counter),
+            Counter::Total => &mut wins.total,
+        }
     }
 }
 
-impl VisualComponent for SingleplayerMatchStats {
-    fn render(&self, area: Rect, buf: &mut Buffer) {
-        let wins = self.wins.get();
-
-        let items = [
-            Text::from("Easy arcade 1CCs:"),
-            Text::from(format!("    {}", wins.n_arcade_easy_1ccs)),
-            Text::from(""),
-            Text::from("Medium arcade 1CCs:"),
-            Text::from(format!("    {}", wins.n_arcade_medium_1ccs)),
-            Text::from(""),
-            Text::from("Hard arcade 1CCs:"),
-            Text::from(format!("    {}", wins.n_arcade_hard_1ccs)),
-            Text::from(""),
-            Text::from("Story 1CCs:"),
-            Text::from(format!("    {}", wins.n_story_1ccs)),
-            Text::from(""),
-            Text::from("Total matches won:"),
-            Text::from(format!("    {}", wins.total)),
-        ];
-        List::new(items).render(area, buf);
+impl std::fmt::Display for Counter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Counter::OneCredit(counter) => write!(f, "{counter}"),
+            Counter::Total => write!(f, "Total matches won"),
+        }
     }
 }
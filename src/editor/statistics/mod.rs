@@ -1,8 +1,10 @@
 mod character_stats;
 mod match_stats;
 
+use player_progress::FileVersion;
 use ratatui::{
     buffer::Buffer,
+    crossterm::event::KeyCode,
     layout::{Constraint, Rect},
     text::Text,
     widgets::{List, Widget},
@@ -10,7 +12,7 @@ use ratatui::{
 
 use crate::{
     editor::content_window::InteratibleTabComponent,
-    savefile::Savefile,
+    savefile::{FileKind, Savefile},
     tui::{Event, HandleEvent, VisualComponent},
     widget::split,
 };
@@ -20,18 +22,45 @@ use self::{character_stats::CharacterStats, match_stats::SingleplayerMatchStats}
 pub struct Tab {
     character_stats: CharacterStats,
     match_stats: SingleplayerMatchStats,
+    pane: Pane,
+    info: InfoText,
 }
 
-struct InfoText;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Pane {
+    Counters,
+    Characters,
+}
+
+struct InfoText {
+    version: String,
+}
 
 impl Tab {
     #[must_use]
     pub fn new(savefile: &Savefile) -> Self {
-        let completion_stats = savefile.progress().read_completion_stats();
-        let wins = savefile.progress().read_wins();
+        let progress = savefile.progress();
+        let arcade = [
+            progress.modify_arcade_easy_1ccs(),
+            progress.modify_arcade_medium_1ccs(),
+            progress.modify_arcade_hard_1ccs(),
+        ];
+        let character_stats = CharacterStats::new(
+            progress.read_completion_stats(),
+            arcade,
+            progress.modify_story_1ccs(),
+        );
+        let match_stats =
+            SingleplayerMatchStats::new(progress.modify_wins(), progress.read_counter_mismatches());
+        let version = match savefile.progress().version() {
+            FileVersion::Known(game) => format!("Save file from game {game}"),
+            FileVersion::Zeroed => "Save file with no game version in it".to_owned(),
+        };
         Self {
-            character_stats: CharacterStats::new(completion_stats),
-            match_stats: SingleplayerMatchStats::new(wins),
+            character_stats,
+            match_stats,
+            pane: Pane::Counters,
+            info: InfoText { version },
         }
     }
 }
@@ -42,7 +71,20 @@ impl InfoText {
 }
 
 impl HandleEvent for Tab {
-    fn handle_event(&mut self, _: &Event) {}
+    fn handle_event(&mut self, event: &Event) {
+        match (self.pane, event.key_code()) {
+            (Pane::Counters, Some(KeyCode::Right)) if !self.match_stats.is_editing() => {
+                self.pane = Pane::Characters;
+            }
+            (Pane::Counters, _) => self.match_stats.handle_event(event),
+            (Pane::Characters, Some(KeyCode::Left))
+                if self.character_stats.is_at_first_column() =>
+            {
+                self.pane = Pane::Counters;
+            }
+            (Pane::Characters, _) => self.character_stats.handle_event(event),
+        }
+    }
 }
 
 impl VisualComponent for Tab {
@@ -50,20 +92,22 @@ impl VisualComponent for Tab {
         let top = split::Area {
             constraint: InfoText::CONSTRAINT,
             render: |area: Rect, buf: &mut Buffer| {
-                InfoText.render(area, buf);
+                self.info.render(area, buf);
             },
         };
 
         let left = split::Area {
             constraint: Constraint::Fill(3),
             render: |area: Rect, buf: &mut Buffer| {
-                self.match_stats.render(area, buf);
+                self.match_stats
+                    .render(area, buf, self.pane == Pane::Counters);
             },
         };
         let right = split::Area {
             constraint: Constraint::Fill(7),
             render: |area: Rect, buf: &mut Buffer| {
-                self.character_stats.render(area, buf);
+                self.character_stats
+                    .render(area, buf, self.pane == Pane::Characters);
             },
         };
 
@@ -82,14 +126,18 @@ impl InteratibleTabComponent for Tab {
     fn name(&self) -> &'static str {
         "Statistics"
     }
+
+    fn files(&self) -> &'static [FileKind] {
+        &[FileKind::Progress]
+    }
 }
 
 impl VisualComponent for InfoText {
     fn render(&self, area: Rect, buf: &mut Buffer) {
         let lines: [Text; InfoText::N_LINES as usize] = [
             Text::from("Statistics from singleplayer matches").centered(),
-            Text::from("").centered(),
-            Text::from("Normally, you unlock stuff based on these stats.").centered(),
+            Text::from(self.version.as_str()).centered(),
+            Text::from("Type to change counters, Enter toggles a 1CC star").centered(),
         ];
         List::new(lines).render(area, buf);
     }
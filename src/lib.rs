@@ -1,12 +1,14 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 
+pub mod cli;
 pub mod savefile;
 
 mod app;
 mod collection;
 mod editor;
 mod limbo;
+mod start;
 mod style;
 mod tui;
 mod widget;
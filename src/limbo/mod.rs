@@ -1,25 +1,280 @@
+use aos2_env::{
+    AoS2Env,
+    process::{self, ProcFs},
+};
+use binary_file::SaveFormat;
 use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::KeyCode;
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Color, Style};
-use ratatui::text::Line;
-use ratatui::widgets::{Block, Borders, Paragraph, Widget, Wrap};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, Paragraph, Widget, Wrap};
 
-use crate::savefile;
-use crate::style::{IndexedColor, WithColor};
-use crate::tui::{HandleEvent, VisualComponent};
+use crate::collection::HoveringIndex;
+use crate::savefile::{
+    self,
+    backup::{Backups, Retention, Snapshot},
+    salvage::{Report, Salvaged},
+};
+use crate::style::{self, IndexedColor, WithColor};
+use crate::tui::{Event, HandleEvent, VisualComponent};
+use crate::widget::{dialog::Dialog, text_input::TextInput};
 
+/// Shows why the files can't be opened, and offers ways out that fit the error.
 pub struct Screen {
     error: savefile::Error,
+    /// Where the files are, if known.
+    env: Option<AoS2Env>,
+    retention: Retention,
+    processes: ProcFs,
+    current_index: usize,
+    mode: Mode,
+    status: Option<String>,
+    outcome: Option<Outcome>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    /// Something was fixed, files in that folder are worth another try.
+    Open(AoS2Env),
+    Quit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    CreateFiles,
+    ReadAnyway,
+    Retry,
+    RestoreBackup,
+    PickFolder,
+    Quit,
+}
+
+enum Mode {
+    Choosing,
+    /// The game would overwrite the fixed files on exit, so ask before writing them.
+    ConfirmingRunningGame(Action),
+    /// Typing the path to another saves folder.
+    Typing(TextInput),
+    PickingBackup {
+        snapshots: Vec<Snapshot>,
+        current_index: usize,
+    },
+    /// The file was read anyway, here's what had to be replaced.
+    Salvaged {
+        report: Report,
+        backup: Snapshot,
+    },
 }
 
 impl Screen {
     pub fn new(error: savefile::Error) -> Self {
-        Self { error }
+        Self {
+            error,
+            env: None,
+            retention: Retention::default(),
+            processes: ProcFs::default(),
+            current_index: 0,
+            mode: Mode::Choosing,
+            status: None,
+            outcome: None,
+        }
+    }
+
+    /// Allows fixing files in place, not only picking another folder.
+    #[must_use]
+    pub fn with_env(mut self, env: AoS2Env) -> Self {
+        self.env = Some(env);
+        self
+    }
+
+    /// Fixes back up the files first, this many backups are kept.
+    #[must_use]
+    pub fn with_backup_retention(mut self, retention: Retention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    #[must_use]
+    pub fn outcome(&self) -> Option<&Outcome> {
+        self.outcome.as_ref()
+    }
+
+    /// Most fitting actions go first.
+    fn actions(&self) -> Vec<Action> {
+        let mut actions = Vec::new();
+        if self.env.is_some() {
+            if self.error.is_missing_file() {
+                actions.push(Action::CreateFiles);
+            }
+            if self.error.damaged_file().is_some() {
+                actions.push(Action::ReadAnyway);
+            }
+            actions.push(Action::Retry);
+            actions.push(Action::RestoreBackup);
+        }
+        actions.extend([Action::PickFolder, Action::Quit]);
+        actions
+    }
+
+    fn backups(&self) -> Option<(&AoS2Env, Backups)> {
+        let env = self.env.as_ref()?;
+        Some((env, Backups::new(env).with_retention(self.retention)))
+    }
+
+    /// Asks first if the action writes files while the game is running,
+    /// otherwise goes on to [`Screen::run`].
+    fn request(&mut self, action: Action) {
+        if action.writes_files() && process::find_game(&self.processes).is_some() {
+            self.mode = Mode::ConfirmingRunningGame(action);
+        } else {
+            self.run(action);
+        }
+    }
+
+    fn run(&mut self, action: Action) {
+        self.status = None;
+        match action {
+            Action::CreateFiles => {
+                if let Some(env) = &self.env {
+                    match savefile::create_missing(env) {
+                        Ok(_) => self.outcome = Some(Outcome::Open(env.clone())),
+                        Err(error) => self.status = Some(error.to_string()),
+                    }
+                }
+            }
+            Action::ReadAnyway => {
+                if let Some((env, backups)) = self.backups()
+                    && let Some(file) = self.error.damaged_file()
+                {
+                    match Salvaged::read(file, env)
+                        .and_then(|salvaged| salvaged.write(env, &backups))
+                    {
+                        Ok((report, backup)) => self.mode = Mode::Salvaged { report, backup },
+                        Err(error) => self.status = Some(error.to_string()),
+                    }
+                }
+            }
+            Action::Retry => self.outcome = self.env.clone().map(Outcome::Open),
+            Action::RestoreBackup => {
+                if let Some((_, backups)) = self.backups() {
+                    match backups.list() {
+                        Ok(snapshots) if snapshots.is_empty() => {
+                            self.status =
+                                Some(format!("No backups in {}", backups.folder().display()));
+                        }
+                        Ok(snapshots) => {
+                            self.mode = Mode::PickingBackup {
+                                snapshots,
+                                current_index: 0,
+                            };
+                        }
+                        Err(error) => self.status = Some(error.to_string()),
+                    }
+                }
+            }
+            Action::PickFolder => {
+                let current = self
+                    .env
+                    .as_ref()
+                    .map(|env| env.saves_folder.display().to_string())
+                    .unwrap_or_default();
+                self.mode = Mode::Typing(TextInput::new(current));
+            }
+            Action::Quit => self.outcome = Some(Outcome::Quit),
+        }
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        let Some((env, backups)) = self.backups() else {
+            return;
+        };
+        match backups.restore(snapshot, env) {
+            Ok(()) => self.outcome = Some(Outcome::Open(env.clone())),
+            Err(error) => {
+                self.mode = Mode::Choosing;
+                self.status = Some(error.to_string());
+            }
+        }
     }
 }
 
 impl HandleEvent for Screen {
-    fn handle_event(&mut self, _: &crate::tui::Event) {}
+    fn handle_event(&mut self, event: &Event) {
+        let Some(key) = event.key_code() else {
+            return;
+        };
+        match &mut self.mode {
+            Mode::Choosing => {
+                let actions = self.actions();
+                let hover =
+                    HoveringIndex::from_collection(&actions).with_current(self.current_index);
+                match key {
+                    KeyCode::Up => {
+                        self.current_index = hover.previous().into_index().unwrap_or_default();
+                    }
+                    KeyCode::Down => {
+                        self.current_index = hover.next().into_index().unwrap_or_default();
+                    }
+                    KeyCode::Enter => {
+                        if let Some(&action) = actions.get(self.current_index) {
+                            self.request(action);
+                        }
+                    }
+                    KeyCode::Esc => self.outcome = Some(Outcome::Quit),
+                    _ => (),
+                }
+            }
+            &mut Mode::ConfirmingRunningGame(action) => match key {
+                KeyCode::Char('y' | 'Y') => {
+                    self.mode = Mode::Choosing;
+                    self.run(action);
+                }
+                KeyCode::Char('n' | 'N') | KeyCode::Esc => self.mode = Mode::Choosing,
+                _ => (),
+            },
+            Mode::Typing(input) => match key {
+                KeyCode::Enter => {
+                    let path = input.text().trim();
+                    if path.is_empty() {
+                        self.status = Some("Type the path to the saves folder".to_owned());
+                    } else {
+                        self.outcome = Some(Outcome::Open(AoS2Env::from_path(path)));
+                    }
+                }
+                KeyCode::Esc => self.mode = Mode::Choosing,
+                key => {
+                    input.handle_key(key);
+                }
+            },
+            Mode::PickingBackup {
+                snapshots,
+                current_index,
+            } => {
+                let hover = HoveringIndex::from_collection(snapshots).with_current(*current_index);
+                match key {
+                    KeyCode::Up => {
+                        *current_index = hover.previous().into_index().unwrap_or_default();
+                    }
+                    KeyCode::Down => {
+                        *current_index = hover.next().into_index().unwrap_or_default();
+                    }
+                    KeyCode::Enter => {
+                        if let Some(snapshot) = snapshots.get(*current_index).cloned() {
+                            self.restore(&snapshot);
+                        }
+                    }
+                    KeyCode::Esc => self.mode = Mode::Choosing,
+                    _ => (),
+                }
+            }
+            Mode::Salvaged { .. } => match key {
+                KeyCode::Enter => self.outcome = self.env.clone().map(Outcome::Open),
+                KeyCode::Esc => self.outcome = Some(Outcome::Quit),
+                _ => (),
+            },
+        }
+    }
 }
 
 impl VisualComponent for Screen {
@@ -62,12 +317,181 @@ impl VisualComponent for Screen {
             )
             .render(all_content_area, buf);
 
+        let [error_area, actions_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(self.actions_height()),
+        ])
+        .areas(padded_content_area);
+        self.draw_actions(actions_area, buf);
+
         match &self.error {
-            savefile::Error::Env(error) => draw_env(error, padded_content_area, buf),
+            savefile::Error::Env(error) => draw_env(error, error_area, buf),
             savefile::Error::Progress(error) => {
-                draw_progress_error(error, padded_content_area, buf);
+                draw_progress_error(error, error_area, buf);
+            }
+            savefile::Error::Profile(error) => draw_profile_error(error, error_area, buf),
+            savefile::Error::Backup(error) => draw_backup_error(error, error_area, buf),
+        }
+
+        if let Mode::ConfirmingRunningGame(_) = self.mode {
+            Dialog::running_game("the fix", "Do it anyway", "Back").render(full_area, buf);
+        }
+    }
+}
+
+impl Screen {
+    /// Header, items, status and the key help.
+    fn actions_height(&self) -> u16 {
+        let n_items = match &self.mode {
+            Mode::Choosing | Mode::ConfirmingRunningGame(_) => self.actions().len(),
+            Mode::Typing(_) => 1,
+            Mode::PickingBackup { snapshots, .. } => snapshots.len().min(10),
+            Mode::Salvaged { report, .. } => report.anomalies.len().clamp(1, 10),
+        };
+        u16::try_from(n_items + 4).unwrap_or(u16::MAX)
+    }
+
+    fn draw_actions(&self, area: Rect, buf: &mut Buffer) {
+        let [header_area, items_area, status_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let key = |text| Span::raw(text).style(Style::new().with_fg(IndexedColor::DarkYellow));
+        let (header, help) = match &self.mode {
+            Mode::Choosing | Mode::ConfirmingRunningGame(_) => (
+                "What now?".to_owned(),
+                vec![
+                    key("Enter"),
+                    Span::raw(" - do it  "),
+                    key("Esc"),
+                    Span::raw(" - exit"),
+                ],
+            ),
+            Mode::Typing(_) => (
+                "Saves folder, where `game.sys` and `player.rkg` are:".to_owned(),
+                vec![
+                    key("Enter"),
+                    Span::raw(" - open  "),
+                    key("Esc"),
+                    Span::raw(" - back"),
+                ],
+            ),
+            Mode::PickingBackup { .. } => (
+                "Backups, newest first (current files are backed up too):".to_owned(),
+                vec![
+                    key("Enter"),
+                    Span::raw(" - restore  "),
+                    key("Esc"),
+                    Span::raw(" - back"),
+                ],
+            ),
+            Mode::Salvaged { report, .. } => (
+                format!(
+                    "Read `{}` anyway, the damaged file is backed up. Replaced with fresh values:",
+                    report.file.file_name()
+                ),
+                vec![
+                    key("Enter"),
+                    Span::raw(" - open the editor  "),
+                    key("Esc"),
+                    Span::raw(" - exit"),
+                ],
+            ),
+        };
+        Line::from(header).render(header_area, buf);
+        Line::from(help).render(help_area, buf);
+
+        self.draw_items(items_area, buf);
+
+        if let Some(status) = &self.status {
+            Paragraph::new(status.replace('\n', " "))
+                .style(Style::new().with_fg(IndexedColor::BrightRed))
+                .render(status_area, buf);
+        }
+    }
+
+    fn draw_items(&self, area: Rect, buf: &mut Buffer) {
+        match &self.mode {
+            Mode::Choosing | Mode::ConfirmingRunningGame(_) => {
+                let lines = self
+                    .actions()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, action)| {
+                        let is_selected = index == self.current_index;
+                        Line::from(action.describe())
+                            .style(style::Selection::from_is_selected(is_selected))
+                    });
+                List::new(lines).render(area, buf);
+            }
+            Mode::Typing(input) => input.widget(true).render(area, buf),
+            Mode::PickingBackup {
+                snapshots,
+                current_index,
+            } => {
+                // Keeps the selected one in view when there are many.
+                let skip = current_index.saturating_sub(usize::from(area.height).saturating_sub(1));
+                let lines = snapshots
+                    .iter()
+                    .enumerate()
+                    .skip(skip)
+                    .map(|(index, snapshot)| {
+                        let is_selected = index == *current_index;
+                        Line::from(snapshot.timestamp.to_string())
+                            .style(style::Selection::from_is_selected(is_selected))
+                    });
+                List::new(lines).render(area, buf);
+            }
+            Mode::Salvaged { report, .. } if !report.is_damaged() => {
+                Line::from("Nothing, the file was fine after all").render(area, buf);
+            }
+            Mode::Salvaged { report, backup } => {
+                let height = usize::from(area.height);
+                let n_shown = if report.anomalies.len() <= height {
+                    report.anomalies.len()
+                } else {
+                    height.saturating_sub(1)
+                };
+                let n_hidden = report.anomalies.len() - n_shown;
+                let mut lines: Vec<Line> = report.anomalies[..n_shown]
+                    .iter()
+                    .map(|anomaly| Line::from(anomaly.to_string()))
+                    .collect();
+                if n_hidden > 0 {
+                    lines.push(Line::from(format!(
+                        "...and {n_hidden} more, all listed in {}",
+                        backup.folder.join(Salvaged::REPORT_FILE_NAME).display()
+                    )));
+                }
+                List::new(lines).render(area, buf);
             }
-            savefile::Error::Profile(error) => draw_profile_error(error, padded_content_area, buf),
+        }
+    }
+}
+
+impl Action {
+    /// Restoring only lists backups at first, but it's better to ask before picking one.
+    fn writes_files(self) -> bool {
+        match self {
+            Action::CreateFiles | Action::ReadAnyway | Action::RestoreBackup => true,
+            Action::Retry | Action::PickFolder | Action::Quit => false,
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Action::CreateFiles => {
+                "Create fresh save files, like the game does on first launch (existing files stay)"
+            }
+            Action::ReadAnyway => "Read the damaged file anyway, replacing what doesn't make sense",
+            Action::Retry => "Try again (e.g. after fixing file permissions)",
+            Action::RestoreBackup => "Restore a backup",
+            Action::PickFolder => "Open another saves folder",
+            Action::Quit => "Exit",
         }
     }
 }
@@ -76,7 +500,8 @@ fn draw_env(error: &aos2_env::Error, area: Rect, buf: &mut Buffer) {
     match error {
         aos2_env::Error::Home => {
             const REASON: &str = "This usually happens due to poor system configuration.";
-            const TIP: &str = "You can try manually specifying save folder location:";
+            const TIP: &str = "You can try manually specifying save folder location, \
+                               or set it in `AOS2_SAVES_DIR`:";
 
             let example = aos2_env::saves_location(aos2_env::EXAMPLE_HOME);
 
@@ -106,6 +531,13 @@ fn draw_profile_error(error: &binary_file::Error, area: Rect, buf: &mut Buffer)
     draw_error_paragraph(text, area, buf);
 }
 
+fn draw_backup_error(error: &savefile::backup::Error, area: Rect, buf: &mut Buffer) {
+    const REASON: &str = "Save files are never written without a fresh backup.";
+
+    let text = format!("Error backing up save files\n\n{error}\n\n{REASON}");
+    draw_error_paragraph(text, area, buf);
+}
+
 fn draw_error_paragraph(text: String, area: Rect, buf: &mut Buffer) {
     Paragraph::new(text)
         .style(
@@ -116,3 +548,42 @@ fn draw_error_paragraph(text: String, area: Rect, buf: &mut Buffer) {
         .wrap(Wrap { trim: false })
         .render(area, buf);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use ratatui::crossterm::event::{Event as RatatuiEvent, KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::{
+        savefile::{Savefile, backup::Backups, temp_saves::TempSaves},
+        tui::{Event, HandleEvent},
+    };
+
+    use super::{Outcome, Screen};
+
+    fn press(screen: &mut Screen, code: KeyCode) {
+        let key = RatatuiEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let event = Event::empty(Instant::now()).follow_with(&key, Instant::now());
+        screen.handle_event(&event);
+    }
+
+    #[rstest::rstest]
+    fn creating_files_asks_first_while_game_runs() {
+        let saves = TempSaves::new();
+        let env = saves.env();
+        let Err(error) = Savefile::load(env.clone()) else {
+            panic!("Precondition: files must be missing");
+        };
+        let mut screen = Screen::new(error).with_env(env.clone());
+        screen.processes = saves.running_game();
+
+        press(&mut screen, KeyCode::Enter);
+        assert!(!env.saves_folder.join(Backups::FILE_NAMES[0]).exists());
+        assert!(screen.outcome().is_none());
+
+        press(&mut screen, KeyCode::Char('y'));
+        assert!(env.saves_folder.join(Backups::FILE_NAMES[0]).exists());
+        assert!(matches!(screen.outcome(), Some(Outcome::Open(_))));
+    }
+}
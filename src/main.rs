@@ -1,23 +1,47 @@
 use std::path::PathBuf;
 
 use anyhow::Context;
+use aos2_save_editor::{
+    cli::{Command, GameIsRunning},
+    savefile::backup::Retention,
+};
 use clap::Parser;
 
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
     /// Path to saves folder (ends with `Documents/Fruitbat Factory/AoS2`).
     saves_folder: Option<PathBuf>,
+    /// How many backups to keep - the oldest ones are deleted.
+    #[arg(long, default_value_t)]
+    keep_backups: Retention,
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
 fn main() -> anyhow::Result<()> {
-    let Args { saves_folder } = Args::parse();
+    let Args {
+        saves_folder,
+        keep_backups,
+        command,
+    } = Args::parse();
+
+    if let Some(command) = command {
+        return match command.run() {
+            Err(error) if error.is::<GameIsRunning>() => {
+                eprintln!("Error: {error}");
+                std::process::exit(GameIsRunning::EXIT_CODE);
+            }
+            result => result,
+        };
+    }
 
     let mut terminal = ratatui::init();
     terminal.clear()?;
 
     let app = match saves_folder {
-        Some(path) => aos2_save_editor::App::from_path(path),
-        None => aos2_save_editor::App::from_env(),
+        Some(path) => aos2_save_editor::App::from_path(path, keep_backups),
+        None => aos2_save_editor::App::from_env(keep_backups),
     };
     let app_result = app.run(&mut terminal);
 
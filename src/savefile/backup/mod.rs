@@ -0,0 +1,297 @@
+mod timestamp;
+
+pub use self::timestamp::{ParseError as TimestampParseError, Timestamp};
+
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+
+use aos2_env::AoS2Env;
+use binary_file::SaveFormat;
+use online_profile::PlayerOnlineProfile;
+use player_progress::PlayerProgress;
+
+/// Rotating snapshots of the save files.
+///
+/// Each snapshot is a folder named after its [`Timestamp`],
+/// with copies of all save files inside.
+#[derive(Debug, Clone)]
+pub struct Backups {
+    folder: PathBuf,
+    retention: Retention,
+}
+
+/// How many snapshots to keep around before deleting the oldest ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, derive_more::FromStr)]
+pub struct Retention(NonZeroUsize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub timestamp: Timestamp,
+    pub folder: PathBuf,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to make a backup in `{}`: {source}", path.display())]
+    Create {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to look for backups in `{}`: {source}", path.display())]
+    List {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to delete an old backup at `{}`: {source}", path.display())]
+    Rotate {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to restore `{}`: {source}", path.display())]
+    Restore {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("No backup named `{0}`")]
+    NotFound(String),
+}
+
+impl Backups {
+    pub const FOLDER_NAME: &'static str = "backups";
+    pub const FILE_NAMES: [&'static str; 2] =
+        [PlayerProgress::FILE_NAME, PlayerOnlineProfile::FILE_NAME];
+
+    #[must_use]
+    pub fn new(env: &AoS2Env) -> Self {
+        Self {
+            folder: env.editor_folder().join(Self::FOLDER_NAME),
+            retention: Retention::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_retention(mut self, retention: Retention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    #[must_use]
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    /// Copies current save files into a new snapshot, then forgets the oldest ones.
+    ///
+    /// Missing save files are skipped - there's nothing to lose anyway.
+    pub fn snapshot(&self, env: &AoS2Env) -> Result<Snapshot, Error> {
+        // Two snapshots in the same second must not overwrite each other.
+        let mut timestamp = Timestamp::now();
+        while self.folder.join(timestamp.to_string()).exists() {
+            timestamp = timestamp.next_second();
+        }
+        let folder = self.folder.join(timestamp.to_string());
+
+        std::fs::create_dir_all(&folder).map_err(|source| Error::Create {
+            path: folder.clone(),
+            source,
+        })?;
+
+        for file_name in Self::FILE_NAMES {
+            let original = env.saves_folder.join(file_name);
+            if !original.exists() {
+                continue;
+            }
+
+            let copy = folder.join(file_name);
+            std::fs::copy(&original, &copy)
+                .map_err(|source| Error::Create { path: copy, source })?;
+        }
+
+        self.rotate()?;
+
+        Ok(Snapshot { timestamp, folder })
+    }
+
+    /// All snapshots, newest first.
+    pub fn list(&self) -> Result<Vec<Snapshot>, Error> {
+        let entries = match std::fs::read_dir(&self.folder) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => {
+                return Err(Error::List {
+                    path: self.folder.clone(),
+                    source,
+                });
+            }
+        };
+
+        let mut snapshots = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|source| Error::List {
+                path: self.folder.clone(),
+                source,
+            })?;
+
+            let timestamp = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<Timestamp>().ok());
+            if let Some(timestamp) = timestamp
+                && entry.path().is_dir()
+            {
+                snapshots.push(Snapshot {
+                    timestamp,
+                    folder: entry.path(),
+                });
+            }
+        }
+
+        snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.timestamp));
+        Ok(snapshots)
+    }
+
+    pub fn find(&self, name: &str) -> Result<Snapshot, Error> {
+        self.list()?
+            .into_iter()
+            .find(|snapshot| snapshot.timestamp.to_string() == name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))
+    }
+
+    /// Puts snapshot files back into the saves folder.
+    ///
+    /// Current files are snapshotted first, so restoring can be undone too.
+    pub fn restore(&self, snapshot: &Snapshot, env: &AoS2Env) -> Result<(), Error> {
+        let mut contents = Vec::new();
+        for file_name in Self::FILE_NAMES {
+            let path = snapshot.folder.join(file_name);
+            if !path.exists() {
+                continue;
+            }
+            let bytes = std::fs::read(&path).map_err(|source| Error::Restore {
+                path: path.clone(),
+                source,
+            })?;
+            contents.push((file_name, bytes));
+        }
+
+        self.snapshot(env)?;
+
+        for (file_name, bytes) in contents {
+            let path = env.saves_folder.join(file_name);
+            binary_file::replace_atomically(&path, &bytes)
+                .map_err(|source| Error::Restore { path, source })?;
+        }
+
+        Ok(())
+    }
+
+    fn rotate(&self) -> Result<(), Error> {
+        for outdated in self.list()?.into_iter().skip(self.retention.get()) {
+            std::fs::remove_dir_all(&outdated.folder).map_err(|source| Error::Rotate {
+                path: outdated.folder,
+                source,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl Retention {
+    #[must_use]
+    pub const fn new(retention: NonZeroUsize) -> Self {
+        Self(retention)
+    }
+
+    #[must_use]
+    pub const fn get(self) -> usize {
+        let Self(retention) = self;
+        retention.get()
+    }
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self(NonZeroUsize::new(10).expect("Invariant: 10 is not zero"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use crate::savefile::temp_saves::TempSaves;
+
+    use super::{Backups, Retention};
+
+    fn saves_with_originals() -> TempSaves {
+        let saves = TempSaves::new();
+        saves.write_all(b"original");
+        saves
+    }
+
+    #[rstest::rstest]
+    fn snapshot_lives_next_to_saves() {
+        let saves = saves_with_originals();
+        let env = saves.env();
+
+        let snapshot = Backups::new(&env).snapshot(&env).expect("Must back up");
+
+        assert!(snapshot.folder.starts_with(env.editor_folder()));
+        for file_name in Backups::FILE_NAMES {
+            let copy = std::fs::read(snapshot.folder.join(file_name)).expect("Must be copied");
+            assert_eq!(b"original".as_slice(), copy);
+        }
+    }
+
+    #[rstest::rstest]
+    fn old_snapshots_are_rotated() {
+        let saves = saves_with_originals();
+        let env = saves.env();
+        let backups = Backups::new(&env)
+            .with_retention(Retention::new(NonZeroUsize::new(2).expect("Not zero")));
+
+        for name in [
+            "2021-03-28_00-00-00",
+            "2021-03-29_00-00-00",
+            "2021-03-30_00-00-00",
+        ] {
+            std::fs::create_dir_all(backups.folder().join(name)).expect("Precondition");
+        }
+        let newest = backups.snapshot(&env).expect("Must back up");
+
+        let remaining: Vec<String> = backups
+            .list()
+            .expect("Must list")
+            .into_iter()
+            .map(|snapshot| snapshot.timestamp.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                newest.timestamp.to_string(),
+                "2021-03-30_00-00-00".to_owned()
+            ],
+            remaining
+        );
+    }
+
+    #[rstest::rstest]
+    fn restores_files() {
+        let saves = saves_with_originals();
+        let env = saves.env();
+        let backups = Backups::new(&env);
+
+        let snapshot = backups.snapshot(&env).expect("Must back up");
+        for file_name in Backups::FILE_NAMES {
+            std::fs::write(env.saves_folder.join(file_name), b"broken").expect("Precondition");
+        }
+
+        backups.restore(&snapshot, &env).expect("Must restore");
+
+        for file_name in Backups::FILE_NAMES {
+            let restored = std::fs::read(env.saves_folder.join(file_name)).expect("Must exist");
+            assert_eq!(b"original".as_slice(), restored);
+        }
+    }
+}
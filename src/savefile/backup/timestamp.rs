@@ -0,0 +1,159 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Seconds since Unix epoch, written as UTC `YYYY-MM-DD_hh-mm-ss`.
+///
+/// Doubles as a backup folder name, so the text form
+/// must sort chronologically and contain no funny characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(u64);
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Expected a timestamp like `2021-03-28_13-37-00`, got `{0}`")]
+pub struct ParseError(String);
+
+struct Civil {
+    year: u64,
+    month: u64,
+    day: u64,
+}
+
+impl Timestamp {
+    const SECONDS_IN_DAY: u64 = 24 * 60 * 60;
+
+    #[must_use]
+    pub fn now() -> Self {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self(since_epoch.as_secs())
+    }
+
+    #[must_use]
+    pub const fn next_second(self) -> Self {
+        let Self(seconds) = self;
+        Self(seconds.saturating_add(1))
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self(seconds) = *self;
+
+        let Civil { year, month, day } = Civil::from_days(seconds / Self::SECONDS_IN_DAY);
+        let time_of_day = seconds % Self::SECONDS_IN_DAY;
+        let (hours, minutes, seconds) =
+            (time_of_day / 3600, time_of_day / 60 % 60, time_of_day % 60);
+
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}_{hours:02}-{minutes:02}-{seconds:02}"
+        )
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseError(s.to_owned());
+
+        let numbers: Vec<u64> = s
+            .split(['-', '_'])
+            .map(|number| number.parse::<u64>().map_err(|_| error()))
+            .collect::<Result<_, _>>()?;
+        let &[year, month, day, hours, minutes, seconds] = numbers.as_slice() else {
+            return Err(error());
+        };
+
+        let is_valid = (1970..=9999).contains(&year)
+            && (1..=12).contains(&month)
+            && (1..=31).contains(&day)
+            && hours < 24
+            && minutes < 60
+            && seconds < 60;
+        if !is_valid {
+            return Err(error());
+        }
+
+        let days = Civil { year, month, day }.into_days();
+        let timestamp = Self(days * Self::SECONDS_IN_DAY + hours * 3600 + minutes * 60 + seconds);
+
+        // Catches dates like February 31st, which "overflow" into the next month.
+        if timestamp.to_string() == s {
+            Ok(timestamp)
+        } else {
+            Err(error())
+        }
+    }
+}
+
+/// Proleptic Gregorian calendar conversions, only for dates after 1970.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html>.
+impl Civil {
+    const DAYS_IN_ERA: u64 = 146_097;
+    const EPOCH_SHIFT: u64 = 719_468;
+
+    fn from_days(days_since_epoch: u64) -> Self {
+        let days = days_since_epoch + Self::EPOCH_SHIFT;
+        let era = days / Self::DAYS_IN_ERA;
+        let day_of_era = days - era * Self::DAYS_IN_ERA;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+        Self { year, month, day }
+    }
+
+    fn into_days(self) -> u64 {
+        let Self { year, month, day } = self;
+
+        let year = year - u64::from(month <= 2);
+        let era = year / 400;
+        let year_of_era = year - era * 400;
+        let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+        let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * Self::DAYS_IN_ERA + day_of_era - Self::EPOCH_SHIFT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Timestamp;
+
+    #[rstest::rstest]
+    #[case::epoch(0, "1970-01-01_00-00-00")]
+    #[case::doc_is_dead(1_616_936_220, "2021-03-28_12-57-00")]
+    #[case::leap_day(1_709_210_096, "2024-02-29_12-34-56")]
+    #[case::new_year_eve(1_767_225_599, "2025-12-31_23-59-59")]
+    fn formats_and_parses_back(#[case] seconds: u64, #[case] text: &str) {
+        let timestamp = Timestamp(seconds);
+
+        assert_eq!(text, timestamp.to_string());
+        assert_eq!(Ok(timestamp), text.parse());
+    }
+
+    #[rstest::rstest]
+    #[case::not_a_date("hehe")]
+    #[case::too_short("2021-03-28")]
+    #[case::no_such_day("2023-02-29_00-00-00")]
+    #[case::no_such_hour("2021-03-28_24-00-00")]
+    #[case::before_epoch("1969-12-31_23-59-59")]
+    fn rejects_garbage(#[case] text: &str) {
+        text.parse::<Timestamp>().expect_err("Must fail here");
+    }
+}
@@ -1,26 +1,68 @@
 use tokio::sync::watch;
 
 /// Ensures channel is never closed by storing both ends together.
+///
+/// Also remembers the last saved value,
+/// so that unsaved changes are simply "current value is different".
+/// Undoing a change by hand makes the channel clean again.
 #[derive(Debug, Clone)]
 pub struct Channel<T> {
     sender: watch::Sender<T>,
     receiver: watch::Receiver<T>,
+    saved: watch::Sender<T>,
 }
 
-impl<T> Channel<T> {
+/// Read-only view on whether a [`Channel`] has unsaved changes.
+#[derive(Debug, Clone)]
+pub struct Dirty<T> {
+    current: watch::Receiver<T>,
+    saved: watch::Receiver<T>,
+}
+
+impl<T: Clone> Channel<T> {
     pub fn new(value: T) -> Self {
+        let (saved, _) = watch::channel(value.clone());
         let (sender, receiver) = watch::channel(value);
-        Channel { sender, receiver }
+        Channel {
+            sender,
+            receiver,
+            saved,
+        }
+    }
+
+    /// The current value, to be written to disk.
+    pub fn current(&self) -> T {
+        self.receiver.borrow().clone()
+    }
+
+    /// Swaps the current value, giving the old one back.
+    pub fn replace(&self, value: T) -> T {
+        self.sender.send_replace(value)
+    }
+
+    /// The value last read from or written to disk.
+    pub fn saved(&self) -> T {
+        self.saved.borrow().clone()
     }
 
-    pub fn has_changed(&self) -> bool {
-        self.receiver
-            .has_changed()
-            .expect("Invariant: channel can't close")
+    pub fn mark_saved(&self, value: T) {
+        self.saved.send_replace(value);
+    }
+}
+
+impl<T> Channel<T> {
+    pub fn is_dirty(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        *self.receiver.borrow() != *self.saved.borrow()
     }
 
-    pub fn borrow_and_update(&mut self) -> watch::Ref<'_, T> {
-        self.receiver.borrow_and_update()
+    pub fn dirty(&self) -> Dirty<T> {
+        Dirty {
+            current: self.receiver.clone(),
+            saved: self.saved.subscribe(),
+        }
     }
 
     pub fn sender(&self) -> watch::Sender<T> {
@@ -31,3 +73,39 @@ impl<T> Channel<T> {
         self.receiver.clone()
     }
 }
+
+impl<T: PartialEq> Dirty<T> {
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        *self.current.borrow() != *self.saved.borrow()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Channel;
+
+    #[rstest::rstest]
+    fn reverted_change_is_clean() {
+        let channel = Channel::new(1);
+        let dirty = channel.dirty();
+
+        channel.sender().send_replace(2);
+        assert!(channel.is_dirty());
+        assert!(dirty.is_dirty());
+
+        channel.sender().send_replace(1);
+        assert!(!channel.is_dirty());
+        assert!(!dirty.is_dirty());
+    }
+
+    #[rstest::rstest]
+    fn saved_change_is_clean() {
+        let channel = Channel::new(1);
+
+        channel.sender().send_replace(2);
+        channel.mark_saved(channel.current());
+
+        assert!(!channel.is_dirty());
+    }
+}
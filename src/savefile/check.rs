@@ -0,0 +1,210 @@
+//! Rules that a save should follow to keep the game happy.
+//!
+//! Nothing here stops you from saving, it only tells what looks off.
+
+use std::fmt::Display;
+
+use online_profile::{PlayerOnlineProfile, avatar, title, unlocks};
+use player_progress::{CounterMismatch, PlayerProgress, Status};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, derive_more::Display)]
+pub enum Severity {
+    /// Weird, but the game copes.
+    Warning,
+    /// The game is likely to crash.
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    TooFewEnabled {
+        category: Category,
+        n_enabled: usize,
+    },
+    BackgroundOutsideSection(avatar::Background),
+    TitleLocked(title::Text),
+    CounterMismatch(CounterMismatch),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
+pub enum Category {
+    #[display("characters")]
+    Characters,
+    #[display("arenas")]
+    Arenas,
+    #[display("music tracks")]
+    Music,
+}
+
+impl Issue {
+    /// Character select crashes with fewer options than that.
+    pub const MIN_ENABLED: usize = 2;
+    /// The game still runs, but picks the same things over and over.
+    pub const RECOMMENDED_ENABLED: usize = 3;
+
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        match self {
+            Issue::TooFewEnabled { n_enabled, .. } if *n_enabled < Self::MIN_ENABLED => {
+                Severity::Error
+            }
+            // Real profiles have those, and the game seems fine with it.
+            Issue::TooFewEnabled { .. }
+            | Issue::BackgroundOutsideSection(_)
+            | Issue::TitleLocked(_)
+            | Issue::CounterMismatch(_) => Severity::Warning,
+        }
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::TooFewEnabled {
+                category,
+                n_enabled,
+            } => write!(
+                f,
+                "Only {n_enabled} {category} enabled, keep at least {}",
+                Self::RECOMMENDED_ENABLED
+            ),
+            Issue::BackgroundOutsideSection(background) => {
+                write!(
+                    f,
+                    "Avatar background `{background}` is not in the unlock list"
+                )
+            }
+            Issue::TitleLocked(text) => write!(f, "Title `{text}` is not unlocked"),
+            Issue::CounterMismatch(mismatch) => write!(f, "{mismatch}"),
+        }
+    }
+}
+
+/// Everything that looks off in both files, errors first.
+#[must_use]
+pub fn check(progress: &PlayerProgress, profile: &PlayerOnlineProfile) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = check_progress(progress)
+        .into_iter()
+        .chain(check_profile(profile))
+        .collect();
+    issues.sort_by_key(|issue| std::cmp::Reverse(issue.severity()));
+    issues
+}
+
+#[must_use]
+pub fn check_progress(progress: &PlayerProgress) -> Vec<Issue> {
+    let categories: [(Category, &[Status]); 3] = [
+        (Category::Characters, progress.playable_characters.as_ref()),
+        (Category::Arenas, progress.arenas.as_ref()),
+        (Category::Music, progress.music_tracks.as_ref()),
+    ];
+    let too_few = categories.into_iter().filter_map(|(category, statuses)| {
+        let n_enabled = statuses.iter().filter(|status| status.is_enabled()).count();
+        (n_enabled < Issue::RECOMMENDED_ENABLED).then_some(Issue::TooFewEnabled {
+            category,
+            n_enabled,
+        })
+    });
+
+    too_few
+        .chain(
+            progress
+                .counter_mismatches()
+                .into_iter()
+                .map(Issue::CounterMismatch),
+        )
+        .collect()
+}
+
+#[must_use]
+pub fn check_profile(profile: &PlayerOnlineProfile) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    let background = profile.avatar_background;
+    if profile.unlockable_backgrounds.get(background).is_err() {
+        issues.push(Issue::BackgroundOutsideSection(background));
+    }
+
+    // These two are always there, no unlock needed.
+    let text = profile.title_text_id;
+    let is_special = matches!(text, title::Text::None | title::Text::Disabled);
+    if !is_special && profile.titles.get(text) != Ok(unlocks::Status::Open) {
+        issues.push(Issue::TitleLocked(text));
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use online_profile::{PlayerOnlineProfile, avatar, title, unlocks};
+    use player_progress::{PlayableCharacters, PlayerProgress, Status};
+
+    use super::{Category, Issue, Severity, check_profile, check_progress};
+
+    #[rstest::rstest]
+    fn fresh_progress_is_fine() {
+        assert_eq!(
+            Vec::<Issue>::new(),
+            check_progress(&PlayerProgress::default())
+        );
+    }
+
+    #[rstest::rstest]
+    #[case::crash(1, Severity::Error)]
+    #[case::risky(2, Severity::Warning)]
+    fn too_few_characters(#[case] n_enabled: usize, #[case] severity: Severity) {
+        let mut progress = PlayerProgress::default();
+        let mut characters = PlayableCharacters::default();
+        for (index, character) in player_progress::Character::members()
+            .into_iter()
+            .enumerate()
+        {
+            characters[character] = if index < n_enabled {
+                Status::Enabled
+            } else {
+                Status::Disabled
+            };
+        }
+        progress.playable_characters = characters;
+
+        let issues = check_progress(&progress);
+
+        let expected = Issue::TooFewEnabled {
+            category: Category::Characters,
+            n_enabled,
+        };
+        assert_eq!(vec![expected.clone()], issues);
+        assert_eq!(severity, expected.severity());
+    }
+
+    #[rstest::rstest]
+    fn silhouette_background_is_reported() {
+        let mut profile = PlayerOnlineProfile::default();
+        profile.avatar_background = avatar::Background::LightGrayBackgroundWithSilhouette;
+
+        let issues = check_profile(&profile);
+
+        assert_eq!(
+            vec![Issue::BackgroundOutsideSection(
+                avatar::Background::LightGrayBackgroundWithSilhouette
+            )],
+            issues
+        );
+    }
+
+    #[rstest::rstest]
+    fn locked_title_is_reported() {
+        let mut profile = PlayerOnlineProfile::default();
+        profile.title_text_id = title::Text::HelloWorld;
+        profile
+            .titles
+            .set(title::Text::HelloWorld, unlocks::Status::Locked)
+            .expect("Precondition: title fits");
+
+        assert_eq!(
+            vec![Issue::TitleLocked(title::Text::HelloWorld)],
+            check_profile(&profile)
+        );
+    }
+}
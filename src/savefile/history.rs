@@ -0,0 +1,136 @@
+use std::collections::VecDeque;
+
+use online_profile::PlayerOnlineProfile;
+use player_progress::PlayerProgress;
+use tokio::sync::watch;
+
+/// Undo/redo stacks shared by every [`Modify`](super::progress::Modify) handle.
+///
+/// One history covers both files, so undo goes back in the order
+/// the user made changes, no matter which tab they were in.
+#[derive(Debug, Clone)]
+pub struct History {
+    stacks: watch::Sender<Stacks>,
+}
+
+/// A file state to go back to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Progress(PlayerProgress),
+    Profile(PlayerOnlineProfile),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Stacks {
+    undo: VecDeque<Change>,
+    redo: Vec<Change>,
+}
+
+impl History {
+    pub const CAPACITY: usize = 100;
+
+    pub fn new() -> Self {
+        let (stacks, _) = watch::channel(Stacks::default());
+        Self { stacks }
+    }
+
+    /// Remembers the state before a new edit.
+    ///
+    /// A new edit makes redoing impossible, like in every text editor.
+    pub fn record(&self, change: Change) {
+        self.stacks.send_modify(|stacks| {
+            stacks.undo.push_back(change);
+            if stacks.undo.len() > Self::CAPACITY {
+                stacks.undo.pop_front();
+            }
+            stacks.redo.clear();
+        });
+    }
+
+    /// Takes the state to go back to, while `revert` swaps it with the current one.
+    pub fn undo(&self, revert: impl FnOnce(Change) -> Change) -> bool {
+        let Some(change) = self.take(|stacks| stacks.undo.pop_back()) else {
+            return false;
+        };
+
+        let current = revert(change);
+        self.stacks.send_modify(|stacks| stacks.redo.push(current));
+        true
+    }
+
+    /// Opposite of [`History::undo`].
+    pub fn redo(&self, revert: impl FnOnce(Change) -> Change) -> bool {
+        let Some(change) = self.take(|stacks| stacks.redo.pop()) else {
+            return false;
+        };
+
+        let current = revert(change);
+        self.stacks
+            .send_modify(|stacks| stacks.undo.push_back(current));
+        true
+    }
+
+    fn take(&self, pop: impl FnOnce(&mut Stacks) -> Option<Change>) -> Option<Change> {
+        let mut change = None;
+        self.stacks.send_modify(|stacks| change = pop(stacks));
+        change
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use player_progress::PlayerProgress;
+
+    use super::{Change, History};
+
+    fn progress_with_id(id: u32) -> Change {
+        let mut progress = PlayerProgress::default();
+        progress.wins.total = id;
+        Change::Progress(progress)
+    }
+
+    #[rstest::rstest]
+    fn undo_then_redo_goes_back_and_forth() {
+        let history = History::new();
+        let mut current = progress_with_id(1);
+        history.record(progress_with_id(0));
+
+        assert!(history.undo(|change| std::mem::replace(&mut current, change)));
+        assert_eq!(progress_with_id(0), current);
+
+        assert!(history.redo(|change| std::mem::replace(&mut current, change)));
+        assert_eq!(progress_with_id(1), current);
+    }
+
+    #[rstest::rstest]
+    fn new_edit_forgets_redo() {
+        let history = History::new();
+        history.record(progress_with_id(0));
+        history.undo(|_| progress_with_id(1));
+
+        history.record(progress_with_id(2));
+
+        assert!(!history.redo(|change| change));
+    }
+
+    #[rstest::rstest]
+    fn oldest_edits_are_forgotten() {
+        let history = History::new();
+        for _ in 0..=History::CAPACITY {
+            history.record(progress_with_id(0));
+        }
+
+        let mut n_undone = 0;
+        while history.undo(|change| change) {
+            n_undone += 1;
+        }
+
+        assert_eq!(History::CAPACITY, n_undone);
+    }
+}
@@ -1,21 +1,59 @@
+pub mod backup;
+pub mod check;
+pub mod preset;
 pub mod profile;
 pub mod progress;
+pub mod salvage;
+pub mod slot;
+pub mod watcher;
 
 mod channel;
+mod history;
+#[cfg(test)]
+pub(crate) mod temp_saves;
+
+pub use self::channel::Dirty;
 
 use std::{fmt::Display, path::PathBuf};
 
 use aos2_env::AoS2Env;
+use binary_file::SaveFormat;
 use online_profile::PlayerOnlineProfile;
 use player_progress::PlayerProgress;
 
-use self::{profile::Profile, progress::Progress};
+use self::{
+    backup::{Backups, Retention},
+    history::{Change, History},
+    preset::Presets,
+    profile::Profile,
+    progress::Progress,
+    watcher::{Conflict, Contents, ExternalChanges, Resolution, Watcher},
+};
 
 #[derive(Debug, Clone)]
 pub struct Savefile {
     aos2_env: AoS2Env,
     progress: Progress,
     profile: Profile,
+    history: History,
+    backups: Backups,
+    watcher: Watcher,
+    /// Only the first write of a session is backed up,
+    /// because that's when the files are still untouched by the editor.
+    is_backed_up: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum FileKind {
+    Progress,
+    Profile,
+}
+
+/// Tells which files have changes that aren't on disk yet.
+#[derive(Debug, Clone)]
+pub struct UnsavedChanges {
+    progress: Dirty<PlayerProgress>,
+    profile: Dirty<PlayerOnlineProfile>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -23,6 +61,7 @@ pub enum Error {
     Env(#[from] aos2_env::Error),
     Progress(binary_file::Error),
     Profile(binary_file::Error),
+    Backup(backup::Error),
 }
 
 impl Savefile {
@@ -37,16 +76,29 @@ impl Savefile {
     }
 
     pub fn load(aos2_env: AoS2Env) -> Result<Self, Error> {
-        let progress = Progress::load(&aos2_env)?;
-        let profile = Profile::load(&aos2_env)?;
+        // Before reading, so that a write right after is still noticed.
+        let watcher = Watcher::new(&aos2_env);
+        let history = History::new();
+        let progress = Progress::load(&aos2_env, history.clone())?;
+        let profile = Profile::load(&aos2_env, history.clone())?;
 
         Ok(Self {
+            backups: Backups::new(&aos2_env),
+            watcher,
             aos2_env,
             progress,
             profile,
+            history,
+            is_backed_up: false,
         })
     }
 
+    #[must_use]
+    pub fn with_backup_retention(mut self, retention: Retention) -> Self {
+        self.backups = self.backups.with_retention(retention);
+        self
+    }
+
     #[must_use]
     pub fn progress(&self) -> &Progress {
         &self.progress
@@ -57,7 +109,149 @@ impl Savefile {
         &self.profile
     }
 
+    #[must_use]
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.progress.has_unsaved_changes() || self.profile.has_unsaved_changes()
+    }
+
+    #[must_use]
+    pub fn read_unsaved_changes(&self) -> UnsavedChanges {
+        UnsavedChanges {
+            progress: self.progress.read_unsaved_changes(),
+            profile: self.profile.read_unsaved_changes(),
+        }
+    }
+
+    /// What looks off in the current, maybe unsaved, state of both files.
+    #[must_use]
+    pub fn check(&self) -> Vec<check::Issue> {
+        check::check(&self.progress.current(), &self.profile.current())
+    }
+
+    #[must_use]
+    pub fn env(&self) -> &AoS2Env {
+        &self.aos2_env
+    }
+
+    #[must_use]
+    pub fn presets(&self) -> Presets {
+        Presets::new(&self.aos2_env)
+    }
+
+    /// Reverts the latest edit in any file.
+    ///
+    /// Returns `false` when there's nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        self.history.undo(|change| self.revert(change))
+    }
+
+    /// Brings back the latest undone edit.
+    ///
+    /// Returns `false` when there's nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        self.history.redo(|change| self.revert(change))
+    }
+
+    fn revert(&self, change: Change) -> Change {
+        match change {
+            Change::Progress(progress) => Change::Progress(self.progress.replace(progress)),
+            Change::Profile(profile) => Change::Profile(self.profile.replace(profile)),
+        }
+    }
+
+    /// Picks up what was written to disk since the last look, usually by the game.
+    ///
+    /// Files without unsaved changes are reloaded right away,
+    /// the rest are left for the user to resolve.
+    /// Files that can't be read yet (e.g. half-written) are looked at again next time.
+    pub fn sync_with_disk(&mut self) -> ExternalChanges {
+        let mut changes = ExternalChanges::default();
+        for (file, stamp) in self.watcher.changed() {
+            let conflict = match file {
+                FileKind::Progress => {
+                    let Ok(disk) = PlayerProgress::load(&self.aos2_env) else {
+                        continue;
+                    };
+                    let saved = self.progress.saved();
+                    let current = self.progress.current();
+                    if disk == saved {
+                        None
+                    } else if current == saved {
+                        self.progress.rebase(disk.clone(), disk);
+                        changes.reloaded.push(file);
+                        None
+                    } else {
+                        Some(Conflict::progress(&saved, &current, disk))
+                    }
+                }
+                FileKind::Profile => {
+                    let Ok(disk) = PlayerOnlineProfile::load(&self.aos2_env) else {
+                        continue;
+                    };
+                    let saved = self.profile.saved();
+                    let current = self.profile.current();
+                    if disk == saved {
+                        None
+                    } else if current == saved {
+                        self.profile.rebase(disk.clone(), disk);
+                        changes.reloaded.push(file);
+                        None
+                    } else {
+                        Some(Conflict::profile(&saved, &current, disk))
+                    }
+                }
+            };
+            self.watcher.mark_seen(file, stamp);
+            changes.conflicts.extend(conflict);
+        }
+        changes
+    }
+
+    /// Settles a file changed both in the editor and on disk.
+    ///
+    /// Whatever replaces the edits can be undone.
+    pub fn resolve(&mut self, conflict: Conflict, resolution: Resolution) {
+        let Conflict { disk, merged, .. } = conflict;
+        let resolved = match resolution {
+            Resolution::Merge => merged,
+            Resolution::TakeDisk => Some(disk.clone()),
+            Resolution::KeepYours => None,
+        };
+
+        match disk {
+            Contents::Progress(disk) => {
+                let current = self.progress.current();
+                let resolved = match resolved {
+                    Some(Contents::Progress(resolved)) => resolved,
+                    _ => current.clone(),
+                };
+                if resolved != current {
+                    self.history.record(Change::Progress(current));
+                }
+                self.progress.rebase(disk, resolved);
+            }
+            Contents::Profile(disk) => {
+                let current = self.profile.current();
+                let resolved = match resolved {
+                    Some(Contents::Profile(resolved)) => resolved,
+                    _ => current.clone(),
+                };
+                if resolved != current {
+                    self.history.record(Change::Profile(current));
+                }
+                self.profile.rebase(disk, resolved);
+            }
+        }
+    }
+
     pub fn save_all(&mut self) -> Result<(), Error> {
+        if self.has_unsaved_changes() && !self.is_backed_up {
+            self.backups
+                .snapshot(&self.aos2_env)
+                .map_err(Error::Backup)?;
+            self.is_backed_up = true;
+        }
+
         self.progress.save(&self.aos2_env)?;
         self.profile.save(&self.aos2_env)?;
 
@@ -65,6 +259,86 @@ impl Savefile {
     }
 }
 
+/// Writes fresh files, like the game makes on first launch, for those that don't exist.
+///
+/// Returns which files were made. Existing files are never touched.
+pub fn create_missing(env: &AoS2Env) -> Result<Vec<FileKind>, Error> {
+    let mut created = Vec::new();
+    if !env.saves_folder.join(PlayerProgress::FILE_NAME).exists() {
+        PlayerProgress::default()
+            .create(env)
+            .map_err(Error::Progress)?;
+        created.push(FileKind::Progress);
+    }
+    if !env
+        .saves_folder
+        .join(PlayerOnlineProfile::FILE_NAME)
+        .exists()
+    {
+        PlayerOnlineProfile::default()
+            .create(env)
+            .map_err(Error::Profile)?;
+        created.push(FileKind::Profile);
+    }
+    Ok(created)
+}
+
+/// Names of things the editor keeps as files or folders, so nothing that can escape them.
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    !name.trim().is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ' '))
+}
+
+impl FileKind {
+    #[must_use]
+    pub fn file_name(self) -> &'static str {
+        match self {
+            FileKind::Progress => PlayerProgress::FILE_NAME,
+            FileKind::Profile => PlayerOnlineProfile::FILE_NAME,
+        }
+    }
+}
+
+impl Error {
+    /// Both files are there when the game was launched at least once,
+    /// otherwise they can be made from scratch.
+    #[must_use]
+    pub fn is_missing_file(&self) -> bool {
+        match self {
+            Error::Progress(error) | Error::Profile(error) => {
+                error.action == binary_file::ErroneousAction::Reading
+                    && matches!(error.detail, binary_file::ErrorDetail::NotFound)
+            }
+            Error::Env(_) | Error::Backup(_) => false,
+        }
+    }
+
+    /// File that exists but can't be read as is, maybe it can be salvaged.
+    #[must_use]
+    pub fn damaged_file(&self) -> Option<FileKind> {
+        let (kind, error) = match self {
+            Error::Progress(error) => (FileKind::Progress, error),
+            Error::Profile(error) => (FileKind::Profile, error),
+            Error::Env(_) | Error::Backup(_) => return None,
+        };
+        (error.action == binary_file::ErroneousAction::Reading
+            && matches!(error.detail, binary_file::ErrorDetail::BinaryFormat(_)))
+        .then_some(kind)
+    }
+}
+
+impl UnsavedChanges {
+    #[must_use]
+    pub fn is_dirty(&self, file: FileKind) -> bool {
+        match file {
+            FileKind::Progress => self.progress.is_dirty(),
+            FileKind::Profile => self.profile.is_dirty(),
+        }
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -77,6 +351,10 @@ impl Display for Error {
                 writeln!(f, "Failed to open `{}`:", PlayerOnlineProfile::FILE_NAME)?;
                 writeln!(f, "- {error}")
             }
+            Error::Backup(error) => {
+                writeln!(f, "Refused to touch save files without a backup:")?;
+                writeln!(f, "- {error}")
+            }
         }
     }
 }
@@ -0,0 +1,331 @@
+//! Named sets of fields to put on top of save files.
+//!
+//! A preset only has the fields it cares about,
+//! everything else in the files is left as is when applying it.
+
+use std::path::{Path, PathBuf};
+
+use aos2_env::AoS2Env;
+use online_profile::{PlayerOnlineProfile, Visibility, avatar, title};
+use player_progress::{Arenas, MusicTracks, PlayableCharacters, PlayerProgress};
+
+/// Stored as JSON, so it's easy to write one by hand too.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Preset {
+    #[serde(default)]
+    pub profile: ProfileFields,
+    #[serde(default)]
+    pub progress: ProgressFields,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_character: Option<avatar::Character>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_background: Option<avatar::Background>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_text_id: Option<title::Text>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_color: Option<title::Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_character_in_background: Option<title::Character>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<Visibility>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingame_title: Option<Visibility>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hitstun_meter: Option<Visibility>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spectators: Option<Visibility>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProgressFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub playable_characters: Option<PlayableCharacters>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arenas: Option<Arenas>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub music_tracks: Option<MusicTracks>,
+}
+
+/// Preset files in the editor folder, one `<name>.json` per preset.
+#[derive(Debug, Clone)]
+pub struct Presets {
+    folder: PathBuf,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to look for presets in `{}`: {source}", path.display())]
+    List {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to read preset `{}`: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Preset `{}` is malformed: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error("Failed to write preset `{}`: {source}", path.display())]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("No preset named `{0}`")]
+    NotFound(String),
+    #[error("`{0}` can't be a preset name, use letters, digits, `-`, `_` or spaces")]
+    InvalidName(String),
+}
+
+impl Preset {
+    /// How the profile looks to others online: avatar, title and what's shown.
+    #[must_use]
+    pub fn from_profile(profile: &PlayerOnlineProfile) -> Self {
+        Self {
+            profile: ProfileFields {
+                avatar_character: Some(profile.avatar_character),
+                avatar_background: Some(profile.avatar_background),
+                title_text_id: Some(profile.title_text_id),
+                title_color: Some(profile.title_color),
+                title_character_in_background: Some(profile.title_character_in_background),
+                country: Some(profile.country),
+                ingame_title: Some(profile.ingame_title),
+                hitstun_meter: Some(profile.hitstun_meter),
+                spectators: Some(profile.spectators),
+            },
+            progress: ProgressFields::default(),
+        }
+    }
+
+    /// Also remembers which characters, arenas and music are enabled.
+    #[must_use]
+    pub fn with_progress(mut self, progress: &PlayerProgress) -> Self {
+        self.progress = ProgressFields {
+            playable_characters: Some(progress.playable_characters.clone()),
+            arenas: Some(progress.arenas.clone()),
+            music_tracks: Some(progress.music_tracks.clone()),
+        };
+        self
+    }
+
+    #[must_use]
+    pub fn has_profile_fields(&self) -> bool {
+        self.profile != ProfileFields::default()
+    }
+
+    #[must_use]
+    pub fn has_progress_fields(&self) -> bool {
+        self.progress != ProgressFields::default()
+    }
+
+    pub fn apply_to_profile(&self, profile: &mut PlayerOnlineProfile) {
+        let ProfileFields {
+            avatar_character,
+            avatar_background,
+            title_text_id,
+            title_color,
+            title_character_in_background,
+            country,
+            ingame_title,
+            hitstun_meter,
+            spectators,
+        } = &self.profile;
+
+        set(&mut profile.avatar_character, avatar_character.as_ref());
+        set(&mut profile.avatar_background, avatar_background.as_ref());
+        set(&mut profile.title_text_id, title_text_id.as_ref());
+        set(&mut profile.title_color, title_color.as_ref());
+        set(
+            &mut profile.title_character_in_background,
+            title_character_in_background.as_ref(),
+        );
+        set(&mut profile.country, country.as_ref());
+        set(&mut profile.ingame_title, ingame_title.as_ref());
+        set(&mut profile.hitstun_meter, hitstun_meter.as_ref());
+        set(&mut profile.spectators, spectators.as_ref());
+    }
+
+    pub fn apply_to_progress(&self, progress: &mut PlayerProgress) {
+        let ProgressFields {
+            playable_characters,
+            arenas,
+            music_tracks,
+        } = &self.progress;
+
+        set(
+            &mut progress.playable_characters,
+            playable_characters.as_ref(),
+        );
+        set(&mut progress.arenas, arenas.as_ref());
+        set(&mut progress.music_tracks, music_tracks.as_ref());
+    }
+}
+
+fn set<T: Clone>(field: &mut T, value: Option<&T>) {
+    if let Some(value) = value {
+        field.clone_from(value);
+    }
+}
+
+impl Presets {
+    pub const FOLDER_NAME: &'static str = "presets";
+    pub const EXTENSION: &'static str = "json";
+
+    #[must_use]
+    pub fn new(env: &AoS2Env) -> Self {
+        Self {
+            folder: env.editor_folder().join(Self::FOLDER_NAME),
+        }
+    }
+
+    #[must_use]
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    /// Names of all presets, sorted.
+    pub fn list(&self) -> Result<Vec<String>, Error> {
+        let entries = match std::fs::read_dir(&self.folder) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => {
+                return Err(Error::List {
+                    path: self.folder.clone(),
+                    source,
+                });
+            }
+        };
+
+        let mut names = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|source| Error::List {
+                path: self.folder.clone(),
+                source,
+            })?;
+
+            let path = entry.path();
+            let is_preset = path.extension().is_some_and(|ext| ext == Self::EXTENSION);
+            if let Some(name) = path.file_stem().and_then(|name| name.to_str())
+                && is_preset
+                && path.is_file()
+            {
+                names.push(name.to_owned());
+            }
+        }
+
+        names.sort();
+        Ok(names)
+    }
+
+    pub fn load(&self, name: &str) -> Result<Preset, Error> {
+        let path = self.path(name)?;
+        let json = match std::fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::NotFound(name.to_owned()));
+            }
+            Err(source) => return Err(Error::Read { path, source }),
+        };
+        serde_json::from_str(&json).map_err(|source| Error::Parse { path, source })
+    }
+
+    /// Overwrites a preset with the same name.
+    pub fn save(&self, name: &str, preset: &Preset) -> Result<PathBuf, Error> {
+        let path = self.path(name)?;
+        let json = serde_json::to_string_pretty(preset).map_err(|source| Error::Write {
+            path: path.clone(),
+            source: source.into(),
+        })?;
+
+        std::fs::create_dir_all(&self.folder).map_err(|source| Error::Write {
+            path: self.folder.clone(),
+            source,
+        })?;
+        binary_file::replace_atomically(&path, json.as_bytes()).map_err(|source| Error::Write {
+            path: path.clone(),
+            source,
+        })?;
+
+        Ok(path)
+    }
+
+    /// Names become file names, so nothing that can escape the folder.
+    fn path(&self, name: &str) -> Result<PathBuf, Error> {
+        if !super::is_valid_name(name) {
+            return Err(Error::InvalidName(name.to_owned()));
+        }
+
+        Ok(self.folder.join(name).with_extension(Self::EXTENSION))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use online_profile::{PlayerOnlineProfile, Visibility, avatar, title};
+
+    use crate::savefile::temp_saves::TempSaves;
+
+    use super::{Error, Preset, Presets, ProfileFields};
+
+    #[rstest::rstest]
+    fn only_preset_fields_are_applied() {
+        let mut profile = PlayerOnlineProfile::default();
+        profile.title_color = title::Color::Red;
+        profile.spectators = Visibility::Show;
+        let preset = Preset {
+            profile: ProfileFields {
+                avatar_character: Some(avatar::Character::Suguri),
+                spectators: Some(Visibility::Hide),
+                ..ProfileFields::default()
+            },
+            ..Preset::default()
+        };
+
+        preset.apply_to_profile(&mut profile);
+
+        let mut expected = PlayerOnlineProfile::default();
+        expected.avatar_character = avatar::Character::Suguri;
+        expected.title_color = title::Color::Red;
+        expected.spectators = Visibility::Hide;
+        assert_eq!(expected, profile);
+    }
+
+    #[rstest::rstest]
+    fn saved_preset_is_listed_and_loaded() {
+        let saves = TempSaves::new();
+        let presets = Presets::new(&saves.env());
+        let preset = Preset::from_profile(&PlayerOnlineProfile::default());
+
+        let path = presets.save("tournament", &preset).expect("Must save");
+
+        assert!(path.starts_with(saves.env().editor_folder()));
+        assert_eq!(
+            vec!["tournament".to_owned()],
+            presets.list().expect("Must list")
+        );
+        assert_eq!(preset, presets.load("tournament").expect("Must load"));
+    }
+
+    #[rstest::rstest]
+    #[case::empty("")]
+    #[case::parent("../oops")]
+    #[case::nested("a/b")]
+    fn bad_names_are_rejected(#[case] name: &str) {
+        let saves = TempSaves::new();
+        let presets = Presets::new(&saves.env());
+
+        let result = presets.save(name, &Preset::default());
+
+        assert!(matches!(result, Err(Error::InvalidName(_))));
+    }
+}
@@ -1,12 +1,22 @@
 use aos2_env::AoS2Env;
-use online_profile::{PlayerOnlineProfile, avatar, title};
+use binary_file::SaveFormat;
+use online_profile::{
+    LobbyName, LobbyPassword, Nickname, PlayerOnlineProfile, Visibility, avatar, title,
+    unlocks::{AvatarsSection, BackgroundsSection, TitlesSection},
+    version::{KnownLayout, Version},
+};
 use tokio::sync::watch;
 
-use super::{Error, channel::Channel};
+use super::{
+    Error,
+    channel::{Channel, Dirty},
+    history::{Change, History},
+};
 
 #[derive(Debug, Clone)]
 pub struct Profile {
     profile: Channel<PlayerOnlineProfile>,
+    history: History,
 }
 
 trait GetFn<T>: Send + Fn(&PlayerOnlineProfile) -> T {}
@@ -18,33 +28,85 @@ impl<A, T> ModifyFn<T> for A where A: Send + Fn(&mut PlayerOnlineProfile, T) {}
 
 pub struct Modify<T> {
     profile: watch::Sender<PlayerOnlineProfile>,
+    history: History,
     write: Box<dyn ModifyFn<T>>,
     get: Box<dyn GetFn<T>>,
 }
 
 impl Profile {
-    pub fn load(env: &AoS2Env) -> Result<Self, Error> {
+    pub fn load(env: &AoS2Env, history: History) -> Result<Self, Error> {
         let profile = PlayerOnlineProfile::load(env).map_err(Error::Profile)?;
         Ok(Self {
             profile: Channel::new(profile),
+            history,
         })
     }
 
+    #[must_use]
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.profile.is_dirty()
+    }
+
     pub fn save(&mut self, env: &AoS2Env) -> Result<(), Error> {
-        if self.profile.has_changed() {
-            self.profile
-                .borrow_and_update()
-                .save(env)
-                .map_err(Error::Profile)
-        } else {
-            Ok(())
+        if !self.has_unsaved_changes() {
+            return Ok(());
         }
+
+        let profile = self.profile.current();
+        profile.save(env).map_err(Error::Profile)?;
+        self.profile.mark_saved(profile);
+
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn current(&self) -> PlayerOnlineProfile {
+        self.profile.current()
+    }
+
+    /// Puts an older value back, without recording it as a new edit.
+    #[must_use]
+    pub fn replace(&self, profile: PlayerOnlineProfile) -> PlayerOnlineProfile {
+        self.profile.replace(profile)
+    }
+
+    #[must_use]
+    pub fn saved(&self) -> PlayerOnlineProfile {
+        self.profile.saved()
+    }
+
+    /// Takes what's on disk as the saved state, with `current` as the edited one on top.
+    pub fn rebase(&self, disk: PlayerOnlineProfile, current: PlayerOnlineProfile) {
+        self.profile.mark_saved(disk);
+        self.profile.replace(current);
+    }
+
+    #[must_use]
+    pub fn version(&self) -> Version {
+        self.profile.receiver().borrow().version
+    }
+
+    #[must_use]
+    pub fn known_layout(&self) -> Option<KnownLayout> {
+        self.profile.receiver().borrow().known_layout()
+    }
+
+    /// Older profiles can be edited as is, but miss newer unlocks.
+    #[must_use]
+    pub fn is_up_to_date(&self) -> bool {
+        self.profile.receiver().borrow().is_up_to_date()
+    }
+
+    #[must_use]
+    pub fn read_unsaved_changes(&self) -> Dirty<PlayerOnlineProfile> {
+        self.profile.dirty()
     }
 
     #[must_use]
     pub fn modify_title_character(&self) -> Modify<title::Character> {
         Modify {
             profile: self.profile.sender(),
+            history: self.history.clone(),
             write: Box::new(
                 |profile: &mut PlayerOnlineProfile, character: title::Character| {
                     profile.title_character_in_background = character;
@@ -58,6 +120,7 @@ impl Profile {
     pub fn modify_title_color(&self) -> Modify<title::Color> {
         Modify {
             profile: self.profile.sender(),
+            history: self.history.clone(),
             write: Box::new(|profile: &mut PlayerOnlineProfile, color: title::Color| {
                 profile.title_color = color;
             }),
@@ -69,6 +132,7 @@ impl Profile {
     pub fn modify_title_text(&self) -> Modify<title::Text> {
         Modify {
             profile: self.profile.sender(),
+            history: self.history.clone(),
             write: Box::new(|profile: &mut PlayerOnlineProfile, text: title::Text| {
                 profile.title_text_id = text;
             }),
@@ -80,6 +144,7 @@ impl Profile {
     pub fn modify_avatar_character(&self) -> Modify<avatar::Character> {
         Modify {
             profile: self.profile.sender(),
+            history: self.history.clone(),
             write: Box::new(
                 |profile: &mut PlayerOnlineProfile, character: avatar::Character| {
                     profile.avatar_character = character;
@@ -93,6 +158,7 @@ impl Profile {
     pub fn modify_avatar_background(&self) -> Modify<avatar::Background> {
         Modify {
             profile: self.profile.sender(),
+            history: self.history.clone(),
             write: Box::new(
                 |profile: &mut PlayerOnlineProfile, background: avatar::Background| {
                     profile.avatar_background = background;
@@ -101,6 +167,155 @@ impl Profile {
             get: Box::new(|profile: &PlayerOnlineProfile| profile.avatar_background),
         }
     }
+
+    #[must_use]
+    pub fn modify_nickname(&self) -> Modify<Nickname> {
+        Modify {
+            profile: self.profile.sender(),
+            history: self.history.clone(),
+            write: Box::new(|profile: &mut PlayerOnlineProfile, nickname: Nickname| {
+                profile.nickname = nickname;
+            }),
+            get: Box::new(|profile: &PlayerOnlineProfile| profile.nickname.clone()),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_lobby_name(&self) -> Modify<LobbyName> {
+        Modify {
+            profile: self.profile.sender(),
+            history: self.history.clone(),
+            write: Box::new(|profile: &mut PlayerOnlineProfile, name: LobbyName| {
+                profile.lobby_name = name;
+            }),
+            get: Box::new(|profile: &PlayerOnlineProfile| profile.lobby_name.clone()),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_lobby_password(&self) -> Modify<LobbyPassword> {
+        Modify {
+            profile: self.profile.sender(),
+            history: self.history.clone(),
+            write: Box::new(
+                |profile: &mut PlayerOnlineProfile, password: LobbyPassword| {
+                    profile.lobby_password = password;
+                },
+            ),
+            get: Box::new(|profile: &PlayerOnlineProfile| profile.lobby_password.clone()),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_unlockable_avatars(&self) -> Modify<AvatarsSection> {
+        Modify {
+            profile: self.profile.sender(),
+            history: self.history.clone(),
+            write: Box::new(
+                |profile: &mut PlayerOnlineProfile, avatars: AvatarsSection| {
+                    profile.unlockable_avatars = avatars;
+                },
+            ),
+            get: Box::new(|profile: &PlayerOnlineProfile| profile.unlockable_avatars.clone()),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_unlockable_backgrounds(&self) -> Modify<BackgroundsSection> {
+        Modify {
+            profile: self.profile.sender(),
+            history: self.history.clone(),
+            write: Box::new(
+                |profile: &mut PlayerOnlineProfile, backgrounds: BackgroundsSection| {
+                    profile.unlockable_backgrounds = backgrounds;
+                },
+            ),
+            get: Box::new(|profile: &PlayerOnlineProfile| profile.unlockable_backgrounds.clone()),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_titles(&self) -> Modify<TitlesSection> {
+        Modify {
+            profile: self.profile.sender(),
+            history: self.history.clone(),
+            write: Box::new(|profile: &mut PlayerOnlineProfile, titles: TitlesSection| {
+                profile.titles = titles;
+            }),
+            get: Box::new(|profile: &PlayerOnlineProfile| profile.titles.clone()),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_country(&self) -> Modify<Visibility> {
+        Modify {
+            profile: self.profile.sender(),
+            history: self.history.clone(),
+            write: Box::new(
+                |profile: &mut PlayerOnlineProfile, visibility: Visibility| {
+                    profile.country = visibility;
+                },
+            ),
+            get: Box::new(|profile: &PlayerOnlineProfile| profile.country),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_ingame_title(&self) -> Modify<Visibility> {
+        Modify {
+            profile: self.profile.sender(),
+            history: self.history.clone(),
+            write: Box::new(
+                |profile: &mut PlayerOnlineProfile, visibility: Visibility| {
+                    profile.ingame_title = visibility;
+                },
+            ),
+            get: Box::new(|profile: &PlayerOnlineProfile| profile.ingame_title),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_hitstun_meter(&self) -> Modify<Visibility> {
+        Modify {
+            profile: self.profile.sender(),
+            history: self.history.clone(),
+            write: Box::new(
+                |profile: &mut PlayerOnlineProfile, visibility: Visibility| {
+                    profile.hitstun_meter = visibility;
+                },
+            ),
+            get: Box::new(|profile: &PlayerOnlineProfile| profile.hitstun_meter),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_spectators(&self) -> Modify<Visibility> {
+        Modify {
+            profile: self.profile.sender(),
+            history: self.history.clone(),
+            write: Box::new(
+                |profile: &mut PlayerOnlineProfile, visibility: Visibility| {
+                    profile.spectators = visibility;
+                },
+            ),
+            get: Box::new(|profile: &PlayerOnlineProfile| profile.spectators),
+        }
+    }
+
+    /// For edits that touch many fields at once, so they undo as one.
+    #[must_use]
+    pub fn modify_whole(&self) -> Modify<PlayerOnlineProfile> {
+        Modify {
+            profile: self.profile.sender(),
+            history: self.history.clone(),
+            write: Box::new(
+                |profile: &mut PlayerOnlineProfile, new: PlayerOnlineProfile| {
+                    *profile = new;
+                },
+            ),
+            get: Box::new(PlayerOnlineProfile::clone),
+        }
+    }
 }
 
 impl<T> Modify<T> {
@@ -111,9 +326,14 @@ impl<T> Modify<T> {
     }
 
     pub fn send(&mut self, value: T) {
+        let previous = self.profile.borrow().clone();
         self.profile.send_modify(|profile| {
             (self.write)(profile, value);
         });
+
+        if *self.profile.borrow() != previous {
+            self.history.record(Change::Profile(previous));
+        }
     }
 }
 
@@ -1,11 +1,16 @@
 use aos2_env::AoS2Env;
+use binary_file::SaveFormat;
 use player_progress::{
-    Arenas, MusicTracks, PerfectArcadeMode, PerfectStoryMode, PlayableCharacters, PlayerProgress,
-    SingleplayerWins,
+    Arenas, CounterMismatch, FileVersion, MusicTracks, PerfectArcadeMode, PerfectStoryMode,
+    PlayableCharacters, PlayerProgress, SingleplayerWins,
 };
 use tokio::sync::watch;
 
-use super::{Error, channel::Channel};
+use super::{
+    Error,
+    channel::{Channel, Dirty},
+    history::{Change, History},
+};
 
 trait GetFn<T>: Send + Fn(&PlayerProgress) -> T {}
 trait ModifyFn<T>: Send + Fn(&mut PlayerProgress, T) {}
@@ -17,6 +22,7 @@ impl<A, T> ModifyFn<T> for A where A: Send + Fn(&mut PlayerProgress, T) {}
 #[derive(Debug, Clone)]
 pub struct Progress {
     progress: Channel<PlayerProgress>,
+    history: History,
 }
 
 #[derive(Debug, Clone)]
@@ -29,6 +35,7 @@ pub struct ComplationStats {
 
 pub struct Modify<T> {
     progress: watch::Sender<PlayerProgress>,
+    history: History,
     write: Box<dyn ModifyFn<T>>,
     get: Box<dyn GetFn<T>>,
 }
@@ -39,23 +46,63 @@ pub struct Read<T> {
 }
 
 impl Progress {
-    pub fn load(env: &AoS2Env) -> Result<Self, Error> {
+    pub fn load(env: &AoS2Env, history: History) -> Result<Self, Error> {
         let progress = PlayerProgress::load(env).map_err(Error::Progress)?;
 
         Ok(Self {
             progress: Channel::new(progress),
+            history,
         })
     }
 
+    #[must_use]
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.progress.is_dirty()
+    }
+
     pub fn save(&mut self, env: &AoS2Env) -> Result<(), Error> {
-        if self.progress.has_changed() {
-            self.progress
-                .borrow_and_update()
-                .save(env)
-                .map_err(Error::Progress)
-        } else {
-            Ok(())
+        if !self.has_unsaved_changes() {
+            return Ok(());
         }
+
+        let progress = self.progress.current();
+        progress.save(env).map_err(Error::Progress)?;
+        self.progress.mark_saved(progress);
+
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn current(&self) -> PlayerProgress {
+        self.progress.current()
+    }
+
+    /// Puts an older value back, without recording it as a new edit.
+    #[must_use]
+    pub fn replace(&self, progress: PlayerProgress) -> PlayerProgress {
+        self.progress.replace(progress)
+    }
+
+    #[must_use]
+    pub fn saved(&self) -> PlayerProgress {
+        self.progress.saved()
+    }
+
+    /// Takes what's on disk as the saved state, with `current` as the edited one on top.
+    pub fn rebase(&self, disk: PlayerProgress, current: PlayerProgress) {
+        self.progress.mark_saved(disk);
+        self.progress.replace(current);
+    }
+
+    /// Game version that wrote the file, if the file says.
+    #[must_use]
+    pub fn version(&self) -> FileVersion {
+        self.progress.receiver().borrow().version()
+    }
+
+    #[must_use]
+    pub fn read_unsaved_changes(&self) -> Dirty<PlayerProgress> {
+        self.progress.dirty()
     }
 
     #[must_use]
@@ -72,17 +119,78 @@ impl Progress {
     }
 
     #[must_use]
-    pub fn read_wins(&self) -> Read<SingleplayerWins> {
+    pub fn read_counter_mismatches(&self) -> Read<Vec<CounterMismatch>> {
         Read {
             progress: self.progress.receiver(),
+            get: Box::new(PlayerProgress::counter_mismatches),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_wins(&self) -> Modify<SingleplayerWins> {
+        Modify {
+            progress: self.progress.sender(),
+            history: self.history.clone(),
+            write: Box::new(|progress: &mut PlayerProgress, wins: SingleplayerWins| {
+                progress.wins = wins;
+            }),
             get: Box::new(|progress: &PlayerProgress| progress.wins.clone()),
         }
     }
 
+    #[must_use]
+    pub fn modify_arcade_easy_1ccs(&self) -> Modify<PerfectArcadeMode> {
+        Modify {
+            progress: self.progress.sender(),
+            history: self.history.clone(),
+            write: Box::new(|progress: &mut PlayerProgress, runs: PerfectArcadeMode| {
+                progress.arcade_easy_1ccs = runs;
+            }),
+            get: Box::new(|progress: &PlayerProgress| progress.arcade_easy_1ccs.clone()),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_arcade_medium_1ccs(&self) -> Modify<PerfectArcadeMode> {
+        Modify {
+            progress: self.progress.sender(),
+            history: self.history.clone(),
+            write: Box::new(|progress: &mut PlayerProgress, runs: PerfectArcadeMode| {
+                progress.arcade_medium_1ccs = runs;
+            }),
+            get: Box::new(|progress: &PlayerProgress| progress.arcade_medium_1ccs.clone()),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_arcade_hard_1ccs(&self) -> Modify<PerfectArcadeMode> {
+        Modify {
+            progress: self.progress.sender(),
+            history: self.history.clone(),
+            write: Box::new(|progress: &mut PlayerProgress, runs: PerfectArcadeMode| {
+                progress.arcade_hard_1ccs = runs;
+            }),
+            get: Box::new(|progress: &PlayerProgress| progress.arcade_hard_1ccs.clone()),
+        }
+    }
+
+    #[must_use]
+    pub fn modify_story_1ccs(&self) -> Modify<PerfectStoryMode> {
+        Modify {
+            progress: self.progress.sender(),
+            history: self.history.clone(),
+            write: Box::new(|progress: &mut PlayerProgress, runs: PerfectStoryMode| {
+                progress.story_1ccs = runs;
+            }),
+            get: Box::new(|progress: &PlayerProgress| progress.story_1ccs.clone()),
+        }
+    }
+
     #[must_use]
     pub fn modify_playable_characters(&self) -> Modify<PlayableCharacters> {
         Modify {
             progress: self.progress.sender(),
+            history: self.history.clone(),
             write: Box::new(
                 |progress: &mut PlayerProgress, characters: PlayableCharacters| {
                     progress.playable_characters = characters;
@@ -96,6 +204,7 @@ impl Progress {
     pub fn modify_arenas(&self) -> Modify<Arenas> {
         Modify {
             progress: self.progress.sender(),
+            history: self.history.clone(),
             write: Box::new(|progress: &mut PlayerProgress, arenas: Arenas| {
                 progress.arenas = arenas;
             }),
@@ -107,19 +216,38 @@ impl Progress {
     pub fn modify_music_tracks(&self) -> Modify<MusicTracks> {
         Modify {
             progress: self.progress.sender(),
+            history: self.history.clone(),
             write: Box::new(|progress: &mut PlayerProgress, music: MusicTracks| {
                 progress.music_tracks = music;
             }),
             get: Box::new(|progress: &PlayerProgress| progress.music_tracks.clone()),
         }
     }
+
+    /// For edits that touch many fields at once, so they undo as one.
+    #[must_use]
+    pub fn modify_whole(&self) -> Modify<PlayerProgress> {
+        Modify {
+            progress: self.progress.sender(),
+            history: self.history.clone(),
+            write: Box::new(|progress: &mut PlayerProgress, new: PlayerProgress| {
+                *progress = new;
+            }),
+            get: Box::new(PlayerProgress::clone),
+        }
+    }
 }
 
 impl<T> Modify<T> {
     pub fn send(&mut self, value: T) {
+        let previous = self.progress.borrow().clone();
         self.progress.send_modify(|progress| {
             (self.write)(progress, value);
         });
+
+        if *self.progress.borrow() != previous {
+            self.history.record(Change::Progress(previous));
+        }
     }
 
     #[must_use]
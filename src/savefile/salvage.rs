@@ -0,0 +1,152 @@
+//! Damaged save files, read anyway.
+//!
+//! Whatever doesn't make sense is replaced with fresh values,
+//! and the report tells what exactly, byte by byte.
+
+use std::{fmt::Display, path::PathBuf};
+
+use aos2_env::AoS2Env;
+use binary_file::{Anomaly, SaveFormat};
+use online_profile::PlayerOnlineProfile;
+use player_progress::PlayerProgress;
+
+use super::{
+    Error, FileKind,
+    backup::{self, Backups, Snapshot},
+};
+
+/// A repaired file, not written yet.
+#[derive(Debug, Clone)]
+pub struct Salvaged {
+    report: Report,
+    repaired: Repaired,
+}
+
+/// What had to be replaced to read the file.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub file: FileKind,
+    pub path: PathBuf,
+    pub anomalies: Vec<Anomaly>,
+}
+
+#[derive(Debug, Clone)]
+enum Repaired {
+    Progress(PlayerProgress),
+    Profile(Box<PlayerOnlineProfile>),
+}
+
+impl Salvaged {
+    /// Kept in the backup of the damaged file.
+    pub const REPORT_FILE_NAME: &'static str = "salvage-report.txt";
+
+    pub fn read(file: FileKind, env: &AoS2Env) -> Result<Self, Error> {
+        let path = env.saves_folder.join(file.file_name());
+        let (anomalies, repaired) = match file {
+            FileKind::Progress => {
+                let player_progress::lenient::Salvaged {
+                    progress,
+                    anomalies,
+                } = player_progress::lenient::from_file(&path).map_err(Error::Progress)?;
+                (anomalies, Repaired::Progress(progress))
+            }
+            FileKind::Profile => {
+                let online_profile::lenient::Salvaged { profile, anomalies } =
+                    online_profile::lenient::from_file(&path).map_err(Error::Profile)?;
+                (anomalies, Repaired::Profile(Box::new(profile)))
+            }
+        };
+
+        Ok(Self {
+            report: Report {
+                file,
+                path,
+                anomalies,
+            },
+            repaired,
+        })
+    }
+
+    #[must_use]
+    pub fn report(&self) -> &Report {
+        &self.report
+    }
+
+    /// Replaces the damaged file, backing it up with the report first.
+    pub fn write(self, env: &AoS2Env, backups: &Backups) -> Result<(Report, Snapshot), Error> {
+        let Self { report, repaired } = self;
+
+        let snapshot = backups.snapshot(env).map_err(Error::Backup)?;
+        let path = snapshot.folder.join(Self::REPORT_FILE_NAME);
+        std::fs::write(&path, format!("{report}\n"))
+            .map_err(|source| Error::Backup(backup::Error::Create { path, source }))?;
+
+        match repaired {
+            Repaired::Progress(progress) => progress.save(env).map_err(Error::Progress)?,
+            Repaired::Profile(profile) => profile.save(env).map_err(Error::Profile)?,
+        }
+        Ok((report, snapshot))
+    }
+}
+
+impl Report {
+    #[must_use]
+    pub fn is_damaged(&self) -> bool {
+        !self.anomalies.is_empty()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            file,
+            path,
+            anomalies,
+        } = self;
+        if anomalies.is_empty() {
+            return write!(f, "`{}` is fine: {}", file.file_name(), path.display());
+        }
+
+        write!(
+            f,
+            "`{}` has {} damaged part(s): {}",
+            file.file_name(),
+            anomalies.len(),
+            path.display()
+        )?;
+        for anomaly in anomalies {
+            write!(f, "\n- {anomaly}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use binary_file::SaveFormat;
+    use player_progress::PlayerProgress;
+
+    use crate::savefile::{FileKind, backup::Backups, temp_saves::TempSaves};
+
+    use super::Salvaged;
+
+    #[rstest::rstest]
+    fn damaged_file_is_backed_up_and_repaired() {
+        let saves = TempSaves::new();
+        let env = saves.env();
+        let path = env.saves_folder.join(PlayerProgress::FILE_NAME);
+        std::fs::write(&path, b"definitely not a save").expect("Precondition: must write");
+        let backups = Backups::new(&env);
+
+        let salvaged = Salvaged::read(FileKind::Progress, &env).expect("Must salvage");
+        assert!(salvaged.report().is_damaged());
+        let (_, snapshot) = salvaged.write(&env, &backups).expect("Must write");
+
+        PlayerProgress::load(&env).expect("Repaired file must be readable");
+        assert!(snapshot.folder.join(Salvaged::REPORT_FILE_NAME).exists());
+        assert_eq!(
+            b"definitely not a save".as_slice(),
+            std::fs::read(snapshot.folder.join(PlayerProgress::FILE_NAME)).expect("Must exist")
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! Named sets of save files, kept by the editor and swapped into the game's folder.
+//!
+//! Each slot is a folder that looks just like the saves folder.
+//! The slot that's currently in the game's folder is "live",
+//! and it's copied back into its slot before another one takes its place,
+//! so whatever happened in the game meanwhile is kept.
+
+use std::path::{Path, PathBuf};
+
+use aos2_env::AoS2Env;
+use binary_file::SaveFormat;
+use online_profile::{PlayerOnlineProfile, title};
+use player_progress::PlayerProgress;
+
+use super::backup::{self, Backups};
+
+#[derive(Debug, Clone)]
+pub struct Slots {
+    folder: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slot {
+    pub name: String,
+    pub folder: PathBuf,
+}
+
+/// What tells slots apart at a glance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub nickname: String,
+    pub n_enabled_characters: usize,
+    pub n_characters: usize,
+    pub title: title::Text,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to look for slots in `{}`: {source}", path.display())]
+    List {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to copy `{}`: {source}", path.display())]
+    Copy {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to remember the live slot in `{}`: {source}", path.display())]
+    Live {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("No slot named `{0}`")]
+    NotFound(String),
+    #[error("`{0}` can't be a slot name, use letters, digits, `-`, `_` or spaces")]
+    InvalidName(String),
+    #[error(transparent)]
+    Backup(#[from] backup::Error),
+}
+
+impl Slots {
+    pub const FOLDER_NAME: &'static str = "slots";
+    /// Holds the name of the live slot.
+    pub const LIVE_FILE_NAME: &'static str = "live.txt";
+
+    #[must_use]
+    pub fn new(env: &AoS2Env) -> Self {
+        Self {
+            folder: env.editor_folder().join(Self::FOLDER_NAME),
+        }
+    }
+
+    #[must_use]
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    /// All slots, sorted by name.
+    pub fn list(&self) -> Result<Vec<Slot>, Error> {
+        let entries = match std::fs::read_dir(&self.folder) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => {
+                return Err(Error::List {
+                    path: self.folder.clone(),
+                    source,
+                });
+            }
+        };
+
+        let mut slots = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|source| Error::List {
+                path: self.folder.clone(),
+                source,
+            })?;
+
+            if let Some(name) = entry.file_name().to_str()
+                && entry.path().is_dir()
+            {
+                slots.push(Slot {
+                    name: name.to_owned(),
+                    folder: entry.path(),
+                });
+            }
+        }
+
+        slots.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(slots)
+    }
+
+    pub fn find(&self, name: &str) -> Result<Slot, Error> {
+        self.list()?
+            .into_iter()
+            .find(|slot| slot.name == name)
+            .ok_or_else(|| Error::NotFound(name.to_owned()))
+    }
+
+    /// Name of the slot that's in the game's folder right now, if any.
+    #[must_use]
+    pub fn live(&self) -> Option<String> {
+        let name = std::fs::read_to_string(self.folder.join(Self::LIVE_FILE_NAME)).ok()?;
+        let name = name.trim();
+        (!name.is_empty()).then(|| name.to_owned())
+    }
+
+    /// Copies files from the game's folder into a slot, making it live.
+    ///
+    /// A slot with the same name is overwritten.
+    pub fn store(&self, name: &str, env: &AoS2Env) -> Result<Slot, Error> {
+        if !super::is_valid_name(name) {
+            return Err(Error::InvalidName(name.to_owned()));
+        }
+
+        let slot = Slot {
+            name: name.to_owned(),
+            folder: self.folder.join(name),
+        };
+        copy_files(&env.saves_folder, &slot.folder)?;
+        self.set_live(name)?;
+
+        Ok(slot)
+    }
+
+    /// Puts a slot into the game's folder, files it doesn't have are removed from there.
+    ///
+    /// The live slot gets the files back first, and the game's folder is backed up too,
+    /// in case it had files that never were in any slot.
+    pub fn switch_to(&self, name: &str, env: &AoS2Env, backups: &Backups) -> Result<(), Error> {
+        let slot = self.find(name)?;
+
+        backups.snapshot(env)?;
+        if let Some(live) = self.live()
+            && live != name
+            && let Ok(live) = self.find(&live)
+        {
+            copy_files(&env.saves_folder, &live.folder)?;
+        }
+
+        copy_files(&slot.folder, &env.saves_folder)?;
+        self.set_live(name)
+    }
+
+    fn set_live(&self, name: &str) -> Result<(), Error> {
+        let path = self.folder.join(Self::LIVE_FILE_NAME);
+        std::fs::write(&path, name).map_err(|source| Error::Live { path, source })
+    }
+}
+
+impl Slot {
+    /// Slot folders look just like the saves folder, so files load the same way.
+    #[must_use]
+    pub fn env(&self) -> AoS2Env {
+        AoS2Env::from_path(&self.folder)
+    }
+}
+
+impl Summary {
+    pub fn load(env: &AoS2Env) -> Result<Self, binary_file::Error> {
+        let progress = PlayerProgress::load(env)?;
+        let profile = PlayerOnlineProfile::load(env)?;
+
+        let characters = progress.playable_characters.as_ref();
+        Ok(Self {
+            nickname: profile.nickname.to_string(),
+            n_enabled_characters: characters
+                .iter()
+                .filter(|status| status.is_enabled())
+                .count(),
+            n_characters: characters.len(),
+            title: profile.title_text_id,
+        })
+    }
+}
+
+/// Makes the save files in `to` exactly like in `from`.
+///
+/// Files missing in `from` are removed from `to`,
+/// so a slot without a profile doesn't keep the previous slot's one live.
+fn copy_files(from: &Path, to: &Path) -> Result<(), Error> {
+    std::fs::create_dir_all(to).map_err(|source| Error::Copy {
+        path: to.to_owned(),
+        source,
+    })?;
+
+    for file_name in Backups::FILE_NAMES {
+        let source_path = from.join(file_name);
+        let target_path = to.join(file_name);
+        let result = if source_path.exists() {
+            std::fs::copy(&source_path, &target_path).map(|_| ())
+        } else {
+            match std::fs::remove_file(&target_path) {
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                other => other,
+            }
+        };
+        result.map_err(|source| Error::Copy {
+            path: source_path,
+            source,
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::savefile::{backup::Backups, temp_saves::TempSaves};
+
+    use super::Slots;
+
+    #[rstest::rstest]
+    fn stored_slot_becomes_live() {
+        let saves = TempSaves::new();
+        saves.write_all(b"main");
+        let slots = Slots::new(&saves.env());
+
+        slots.store("main", &saves.env()).expect("Must store");
+
+        assert_eq!(Some("main".to_owned()), slots.live());
+        let names: Vec<String> = slots
+            .list()
+            .expect("Must list")
+            .into_iter()
+            .map(|slot| slot.name)
+            .collect();
+        assert_eq!(vec!["main".to_owned()], names);
+    }
+
+    #[rstest::rstest]
+    fn switching_keeps_live_changes() {
+        let saves = TempSaves::new();
+        let env = saves.env();
+        let slots = Slots::new(&env);
+        let backups = Backups::new(&env);
+        saves.write_all(b"alt");
+        slots.store("alt", &env).expect("Precondition: must store");
+        saves.write_all(b"main");
+        slots.store("main", &env).expect("Precondition: must store");
+
+        saves.write_all(b"main, played some more");
+        slots.switch_to("alt", &env, &backups).expect("Must switch");
+        assert_eq!(b"alt".as_slice(), saves.read(Backups::FILE_NAMES[0]));
+
+        slots
+            .switch_to("main", &env, &backups)
+            .expect("Must switch");
+        assert_eq!(
+            b"main, played some more".as_slice(),
+            saves.read(Backups::FILE_NAMES[0])
+        );
+    }
+
+    #[rstest::rstest]
+    fn files_missing_in_slot_are_not_left_live() {
+        let saves = TempSaves::new();
+        let env = saves.env();
+        let slots = Slots::new(&env);
+        let backups = Backups::new(&env);
+        let [progress, profile] = Backups::FILE_NAMES;
+        saves.write_all(b"main");
+        slots.store("main", &env).expect("Precondition: must store");
+        std::fs::remove_file(env.saves_folder.join(profile)).expect("Precondition: must remove");
+        slots.store("alt", &env).expect("Precondition: must store");
+        slots
+            .switch_to("main", &env, &backups)
+            .expect("Precondition: must switch");
+
+        slots.switch_to("alt", &env, &backups).expect("Must switch");
+        assert_eq!(b"main".as_slice(), saves.read(progress));
+        assert!(!env.saves_folder.join(profile).exists());
+
+        slots
+            .switch_to("main", &env, &backups)
+            .expect("Must switch");
+        assert!(
+            !slots
+                .find("alt")
+                .expect("Must exist")
+                .folder
+                .join(profile)
+                .exists()
+        );
+        assert_eq!(b"main".as_slice(), saves.read(profile));
+    }
+
+    #[rstest::rstest]
+    fn missing_slot_is_not_found() {
+        let saves = TempSaves::new();
+        let env = saves.env();
+
+        let result = Slots::new(&env).switch_to("nope", &env, &Backups::new(&env));
+
+        assert!(matches!(result, Err(super::Error::NotFound(_))));
+    }
+}
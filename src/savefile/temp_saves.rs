@@ -0,0 +1,46 @@
+//! Throwaway saves folders for tests.
+
+use aos2_env::{AoS2Env, process::ProcFs};
+
+use crate::savefile::backup::Backups;
+
+/// An empty `AoS2` saves folder in its own temp dir, gone on drop.
+///
+/// Every test gets a fresh dir, so tests can run in parallel.
+pub struct TempSaves(tempfile::TempDir);
+
+impl TempSaves {
+    pub fn new() -> Self {
+        let root = tempfile::Builder::new()
+            .prefix("aos2-save-editor-tests")
+            .tempdir()
+            .expect("Precondition: must create a temp dir");
+        std::fs::create_dir(root.path().join("AoS2")).expect("Precondition: must create saves");
+        Self(root)
+    }
+
+    pub fn env(&self) -> AoS2Env {
+        AoS2Env::from_path(self.0.path().join("AoS2"))
+    }
+
+    /// Puts the same bytes in every save file.
+    pub fn write_all(&self, content: &[u8]) {
+        for file_name in Backups::FILE_NAMES {
+            std::fs::write(self.env().saves_folder.join(file_name), content)
+                .expect("Precondition: must write a save file");
+        }
+    }
+
+    pub fn read(&self, file_name: &str) -> Vec<u8> {
+        std::fs::read(self.env().saves_folder.join(file_name)).expect("Must exist")
+    }
+
+    /// A `/proc` next to the saves, where the game is running.
+    pub fn running_game(&self) -> ProcFs {
+        let pid_folder = self.0.path().join("proc").join("4242");
+        std::fs::create_dir_all(&pid_folder).expect("Precondition: must create a process");
+        std::fs::write(pid_folder.join("cmdline"), b"AoS2.exe\0")
+            .expect("Precondition: must write a command line");
+        ProcFs::at(self.0.path().join("proc"))
+    }
+}
@@ -0,0 +1,417 @@
+//! Notices when something else, usually the game, rewrites the save files.
+
+use std::{
+    fmt::Display,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+    time::Duration,
+};
+
+use aos2_env::AoS2Env;
+use online_profile::PlayerOnlineProfile;
+use player_progress::PlayerProgress;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use super::FileKind;
+
+/// Polls file contents, because there are only two tiny files,
+/// and modification times may not change on quick writes.
+#[derive(Debug, Clone)]
+pub struct Watcher {
+    saves_folder: PathBuf,
+    progress: Stamp,
+    profile: Stamp,
+}
+
+/// Fingerprint of file contents, or nothing if it can't be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stamp(Option<u64>);
+
+/// What the files got while the editor had them open.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExternalChanges {
+    /// Files without unsaved changes, which now show what's on disk.
+    pub reloaded: Vec<FileKind>,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Both the editor and something else changed the same file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub file: FileKind,
+    pub differences: Vec<Difference>,
+    pub(super) disk: Contents,
+    /// Nothing when the edits can't be combined into a valid file.
+    pub(super) merged: Option<Contents>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Contents {
+    Progress(PlayerProgress),
+    Profile(PlayerOnlineProfile),
+}
+
+/// A field that's different in the editor and on disk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difference {
+    pub path: String,
+    pub yours: Value,
+    pub disk: Value,
+    pub changed_by: ChangedBy,
+}
+
+/// Who changed the field since the file was last loaded or saved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangedBy {
+    You,
+    Disk,
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    /// Take fields changed on disk, keep fields changed in the editor.
+    Merge,
+    /// Next save overwrites what's on disk.
+    KeepYours,
+    /// Unsaved changes in the file are dropped.
+    TakeDisk,
+}
+
+impl Watcher {
+    pub const INTERVAL: Duration = Duration::from_millis(500);
+
+    #[must_use]
+    pub fn new(env: &AoS2Env) -> Self {
+        let mut watcher = Self {
+            saves_folder: env.saves_folder.clone(),
+            progress: Stamp(None),
+            profile: Stamp(None),
+        };
+        watcher.progress = watcher.stamp(FileKind::Progress);
+        watcher.profile = watcher.stamp(FileKind::Profile);
+        watcher
+    }
+
+    /// Files written since they were last seen, with stamps to mark them seen with.
+    #[must_use]
+    pub fn changed(&self) -> Vec<(FileKind, Stamp)> {
+        [
+            (FileKind::Progress, self.progress),
+            (FileKind::Profile, self.profile),
+        ]
+        .into_iter()
+        .filter_map(|(file, seen)| {
+            let stamp = self.stamp(file);
+            (stamp != seen).then_some((file, stamp))
+        })
+        .collect()
+    }
+
+    /// Stops reporting the file until it's written again.
+    ///
+    /// The stamp must be taken before reading the file,
+    /// so that a write in between is noticed next time.
+    pub fn mark_seen(&mut self, file: FileKind, stamp: Stamp) {
+        match file {
+            FileKind::Progress => self.progress = stamp,
+            FileKind::Profile => self.profile = stamp,
+        }
+    }
+
+    fn stamp(&self, file: FileKind) -> Stamp {
+        let fingerprint = std::fs::read(self.saves_folder.join(file.file_name()))
+            .ok()
+            .map(|contents| {
+                let mut hasher = DefaultHasher::new();
+                contents.hash(&mut hasher);
+                hasher.finish()
+            });
+        Stamp(fingerprint)
+    }
+}
+
+impl Conflict {
+    #[must_use]
+    pub fn progress(saved: &PlayerProgress, yours: &PlayerProgress, disk: PlayerProgress) -> Self {
+        Self {
+            file: FileKind::Progress,
+            differences: differences(saved, yours, &disk),
+            merged: merge(saved, yours, &disk).map(Contents::Progress),
+            disk: Contents::Progress(disk),
+        }
+    }
+
+    #[must_use]
+    pub fn profile(
+        saved: &PlayerOnlineProfile,
+        yours: &PlayerOnlineProfile,
+        disk: PlayerOnlineProfile,
+    ) -> Self {
+        Self {
+            file: FileKind::Profile,
+            differences: differences(saved, yours, &disk),
+            merged: merge(saved, yours, &disk).map(Contents::Profile),
+            disk: Contents::Profile(disk),
+        }
+    }
+
+    #[must_use]
+    pub fn can_merge(&self) -> bool {
+        self.merged.is_some()
+    }
+}
+
+impl Difference {
+    /// The value in the editor, as it'd be typed, and short enough to fit in a dialog.
+    #[must_use]
+    pub fn yours_text(&self) -> String {
+        short(&self.yours)
+    }
+
+    #[must_use]
+    pub fn disk_text(&self) -> String {
+        short(&self.disk)
+    }
+}
+
+impl Display for ChangedBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            ChangedBy::You => "you",
+            ChangedBy::Disk => "disk",
+            ChangedBy::Both => "both",
+        };
+        f.write_str(text)
+    }
+}
+
+/// Fields that differ between the editor and the disk, relative to the last saved state.
+fn differences<T: Serialize>(saved: &T, yours: &T, disk: &T) -> Vec<Difference> {
+    let (Ok(saved), Ok(yours), Ok(disk)) = (
+        serde_json::to_value(saved),
+        serde_json::to_value(yours),
+        serde_json::to_value(disk),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut differences = Vec::new();
+    collect_differences(String::new(), &saved, &yours, &disk, &mut differences);
+    differences
+}
+
+fn collect_differences(
+    path: String,
+    saved: &Value,
+    yours: &Value,
+    disk: &Value,
+    out: &mut Vec<Difference>,
+) {
+    if let Some(children) = children(saved, yours, disk) {
+        for (key, saved, yours, disk) in children {
+            collect_differences(join(&path, &key), saved, yours, disk, out);
+        }
+        return;
+    }
+
+    if yours == disk {
+        return;
+    }
+    let changed_by = match (yours != saved, disk != saved) {
+        (true, false) => ChangedBy::You,
+        (false, true) => ChangedBy::Disk,
+        _ => ChangedBy::Both,
+    };
+    out.push(Difference {
+        path,
+        yours: yours.clone(),
+        disk: disk.clone(),
+        changed_by,
+    });
+}
+
+/// Takes every field changed on disk unless it was changed in the editor too.
+fn merge<T: Serialize + DeserializeOwned>(saved: &T, yours: &T, disk: &T) -> Option<T> {
+    let saved = serde_json::to_value(saved).ok()?;
+    let mut merged = serde_json::to_value(yours).ok()?;
+    let disk = serde_json::to_value(disk).ok()?;
+
+    merge_into(&mut merged, &saved, &disk);
+    serde_json::from_value(merged).ok()
+}
+
+fn merge_into(yours: &mut Value, saved: &Value, disk: &Value) {
+    match (yours, saved, disk) {
+        (Value::Object(yours), Value::Object(saved), Value::Object(disk)) => {
+            for (key, value) in yours {
+                if let (Some(saved), Some(disk)) = (saved.get(key), disk.get(key)) {
+                    merge_into(value, saved, disk);
+                }
+            }
+        }
+        (Value::Array(yours), Value::Array(saved), Value::Array(disk))
+            if yours.len() == saved.len() && yours.len() == disk.len() =>
+        {
+            for ((value, saved), disk) in yours.iter_mut().zip(saved).zip(disk) {
+                merge_into(value, saved, disk);
+            }
+        }
+        (yours, saved, disk) => {
+            if yours == saved {
+                *yours = disk.clone();
+            }
+        }
+    }
+}
+
+/// Fields of an object, or items of an array, when all three have the same shape.
+///
+/// Arrays of different lengths are compared as a whole.
+fn children<'a>(
+    saved: &'a Value,
+    yours: &'a Value,
+    disk: &'a Value,
+) -> Option<Vec<(String, &'a Value, &'a Value, &'a Value)>> {
+    match (saved, yours, disk) {
+        (Value::Object(saved), Value::Object(yours), Value::Object(disk)) => Some(
+            saved
+                .iter()
+                .filter_map(|(key, saved)| {
+                    Some((key.clone(), saved, yours.get(key)?, disk.get(key)?))
+                })
+                .collect(),
+        ),
+        (Value::Array(saved), Value::Array(yours), Value::Array(disk))
+            if saved.len() == yours.len() && saved.len() == disk.len() =>
+        {
+            Some(
+                saved
+                    .iter()
+                    .zip(yours)
+                    .zip(disk)
+                    .enumerate()
+                    .map(|(index, ((saved, yours), disk))| {
+                        (format!("[{index}]"), saved, yours, disk)
+                    })
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() || key.starts_with('[') {
+        format!("{path}{key}")
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+fn short(value: &Value) -> String {
+    const MAX_LENGTH: usize = 24;
+
+    let text = match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    if text.chars().count() > MAX_LENGTH {
+        let cut: String = text.chars().take(MAX_LENGTH - 3).collect();
+        format!("{cut}...")
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use binary_file::SaveFormat;
+    use online_profile::{PlayerOnlineProfile, Visibility};
+    use player_progress::{Character, PlayerProgress, Status};
+
+    use crate::savefile::{self, FileKind, Savefile, temp_saves::TempSaves};
+
+    use super::{ChangedBy, Conflict, Contents};
+
+    #[rstest::rstest]
+    fn merge_takes_disk_fields_and_keeps_yours() {
+        let saved = PlayerProgress::default();
+        let mut yours = saved.clone();
+        yours.playable_characters[Character::Sora] = Status::Disabled;
+        let mut disk = saved.clone();
+        disk.wins.total = 7;
+
+        let conflict = Conflict::progress(&saved, &yours, disk);
+
+        let Some(Contents::Progress(merged)) = conflict.merged else {
+            panic!("Must merge");
+        };
+        assert_eq!(
+            Status::Disabled,
+            merged.playable_characters[Character::Sora]
+        );
+        assert_eq!(7, merged.wins.total);
+        let changed_by: Vec<ChangedBy> = conflict
+            .differences
+            .iter()
+            .map(|difference| difference.changed_by)
+            .collect();
+        assert_eq!(vec![ChangedBy::You, ChangedBy::Disk], changed_by);
+    }
+
+    #[rstest::rstest]
+    fn field_changed_on_both_sides_keeps_yours_on_merge() {
+        let saved = PlayerProgress::default();
+        let mut yours = saved.clone();
+        yours.wins.total = 3;
+        let mut disk = saved.clone();
+        disk.wins.total = 5;
+
+        let conflict = Conflict::progress(&saved, &yours, disk);
+
+        let Some(Contents::Progress(merged)) = conflict.merged else {
+            panic!("Must merge");
+        };
+        assert_eq!(3, merged.wins.total);
+        assert_eq!(ChangedBy::Both, conflict.differences[0].changed_by);
+        assert_eq!("wins.total", conflict.differences[0].path);
+        assert_eq!("3", conflict.differences[0].yours_text());
+        assert_eq!("5", conflict.differences[0].disk_text());
+    }
+
+    #[rstest::rstest]
+    fn untouched_file_is_reloaded_and_edited_one_conflicts() {
+        let saves = TempSaves::new();
+        let env = saves.env();
+        savefile::create_missing(&env).expect("Precondition: must create files");
+        let mut savefile = Savefile::load(env.clone()).expect("Precondition: must load");
+        savefile.profile().modify_nickname().send(
+            "edited"
+                .to_owned()
+                .try_into()
+                .expect("Precondition: valid nickname"),
+        );
+
+        let mut progress = PlayerProgress::default();
+        progress.wins.total = 42;
+        progress.save(&env).expect("Precondition: must write");
+        let mut profile = PlayerOnlineProfile::default();
+        profile.country = Visibility::Show;
+        profile.save(&env).expect("Precondition: must write");
+
+        let changes = savefile.sync_with_disk();
+
+        assert_eq!(vec![FileKind::Progress], changes.reloaded);
+        assert_eq!(42, savefile.progress().current().wins.total);
+        let [conflict] = changes.conflicts.as_slice() else {
+            panic!("Profile must conflict");
+        };
+        assert_eq!(FileKind::Profile, conflict.file);
+        assert!(
+            savefile.sync_with_disk().conflicts.is_empty(),
+            "Seen writes must not repeat"
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use aos2_env::{
+    AoS2Env,
+    process::{self, ProcFs},
+};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{List, Paragraph, Widget},
+};
+
+use crate::{
+    collection::HoveringIndex,
+    savefile::{
+        self, Savefile,
+        backup::{Backups, Retention},
+        slot::{Slot, Slots, Summary},
+    },
+    style::{self, IndexedColor, WithColor},
+    tui::{Event, HandleEvent, VisualComponent},
+    widget::{dialog::Dialog, text_input::TextInput},
+};
+
+/// Picks which save slot to put into the game's folder before editing it.
+pub struct Screen {
+    env: AoS2Env,
+    retention: Retention,
+    slots: Slots,
+    processes: ProcFs,
+    rows: Vec<Row>,
+    current_index: usize,
+    mode: Mode,
+    status: Status,
+    outcome: Option<Outcome>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Edit,
+    Quit,
+}
+
+enum Mode {
+    Picking,
+    /// Typing a name to store the game's folder under.
+    Naming(TextInput),
+    /// The game would overwrite the switched slot on exit, so ask before switching.
+    ConfirmingRunningGame,
+}
+
+enum Status {
+    Idle,
+    Done(String),
+    Failed(String),
+}
+
+struct Row {
+    /// The game's folder when it doesn't belong to any slot.
+    slot: Option<Slot>,
+    is_live: bool,
+    summary: Result<Summary, String>,
+}
+
+impl Screen {
+    pub const NEW_SLOT_KEY: KeyCode = KeyCode::F(2);
+
+    pub fn new(env: AoS2Env, retention: Retention) -> Self {
+        let mut screen = Self {
+            slots: Slots::new(&env),
+            env,
+            retention,
+            processes: ProcFs::default(),
+            rows: Vec::new(),
+            current_index: 0,
+            mode: Mode::Picking,
+            status: Status::Idle,
+            outcome: None,
+        };
+        screen.reload();
+        screen
+    }
+
+    #[must_use]
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    #[must_use]
+    pub fn env(&self) -> &AoS2Env {
+        &self.env
+    }
+
+    /// Files in the game's folder, ready to be edited.
+    pub fn open(&self) -> Result<Savefile, savefile::Error> {
+        Savefile::load(self.env.clone())
+            .map(|savefile| savefile.with_backup_retention(self.retention))
+    }
+
+    fn reload(&mut self) {
+        let slots = match self.slots.list() {
+            Ok(slots) => slots,
+            Err(error) => {
+                self.status = Status::Failed(error.to_string());
+                Vec::new()
+            }
+        };
+        let live = self.slots.live();
+
+        // The live slot may be out of date, the game's folder never is.
+        let summary = |env: &AoS2Env| Summary::load(env).map_err(|error| error.to_string());
+        let mut rows: Vec<Row> = slots
+            .into_iter()
+            .map(|slot| {
+                let is_live = live.as_deref() == Some(slot.name.as_str());
+                let env = if is_live {
+                    self.env.clone()
+                } else {
+                    slot.env()
+                };
+                Row {
+                    summary: summary(&env),
+                    slot: Some(slot),
+                    is_live,
+                }
+            })
+            .collect();
+        if !rows.iter().any(|row| row.is_live) {
+            rows.insert(
+                0,
+                Row {
+                    slot: None,
+                    is_live: true,
+                    summary: summary(&self.env),
+                },
+            );
+        }
+
+        self.current_index = rows.iter().position(|row| row.is_live).unwrap_or_default();
+        self.rows = rows;
+    }
+
+    /// The slot to switch to, if the picked one isn't live already.
+    fn picked_slot(&self) -> Option<&Slot> {
+        let row = self.rows.get(self.current_index)?;
+        row.slot.as_ref().filter(|_| !row.is_live)
+    }
+
+    /// Asks first if switching while the game is running, otherwise goes on to [`Screen::pick`].
+    fn request_pick(&mut self) {
+        if self.picked_slot().is_some() && process::find_game(&self.processes).is_some() {
+            self.mode = Mode::ConfirmingRunningGame;
+        } else {
+            self.pick();
+        }
+    }
+
+    fn pick(&mut self) {
+        let Some(slot) = self.picked_slot() else {
+            self.outcome = Some(Outcome::Edit);
+            return;
+        };
+        let backups = Backups::new(&self.env).with_retention(self.retention);
+        match self.slots.switch_to(&slot.name, &self.env, &backups) {
+            Ok(()) => self.outcome = Some(Outcome::Edit),
+            Err(error) => self.status = Status::Failed(error.to_string()),
+        }
+    }
+
+    fn store(&mut self, name: &str) {
+        match self.slots.store(name, &self.env) {
+            Ok(slot) => {
+                self.reload();
+                self.status = Status::Done(format!("Stored the game's folder as `{}`", slot.name));
+            }
+            Err(error) => self.status = Status::Failed(error.to_string()),
+        }
+    }
+}
+
+impl HandleEvent for Screen {
+    fn handle_event(&mut self, event: &Event) {
+        match &mut self.mode {
+            Mode::Picking => {
+                let hover =
+                    HoveringIndex::from_collection(&self.rows).with_current(self.current_index);
+                match event.key_code() {
+                    Some(KeyCode::Up) => {
+                        self.current_index = hover.previous().into_index().unwrap_or_default();
+                    }
+                    Some(KeyCode::Down) => {
+                        self.current_index = hover.next().into_index().unwrap_or_default();
+                    }
+                    Some(KeyCode::Enter) => self.request_pick(),
+                    Some(Self::NEW_SLOT_KEY) => self.mode = Mode::Naming(TextInput::default()),
+                    Some(KeyCode::Esc) => self.outcome = Some(Outcome::Quit),
+                    _ => (),
+                }
+            }
+            Mode::Naming(input) => match event.key_code() {
+                Some(KeyCode::Enter) => {
+                    let name = input.text().to_owned();
+                    self.mode = Mode::Picking;
+                    self.store(&name);
+                }
+                Some(KeyCode::Esc) => self.mode = Mode::Picking,
+                Some(key) => {
+                    input.handle_key(key);
+                }
+                None => (),
+            },
+            Mode::ConfirmingRunningGame => match event.key_code() {
+                Some(KeyCode::Char('y' | 'Y')) => {
+                    self.mode = Mode::Picking;
+                    self.pick();
+                }
+                Some(KeyCode::Char('n' | 'N') | KeyCode::Esc) => self.mode = Mode::Picking,
+                _ => (),
+            },
+        }
+    }
+}
+
+impl VisualComponent for Screen {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let constraints = [
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ];
+        let [title_area, _, list_area, status_area, footer_area] =
+            Layout::vertical(constraints).areas(area);
+
+        Line::from("AoS2 Save Editor - pick a save slot")
+            .style(
+                Style::new()
+                    .with_bg(IndexedColor::DarkBlue)
+                    .with_fg(Color::White),
+            )
+            .centered()
+            .render(title_area, buf);
+
+        let lines = self.rows.iter().enumerate().map(|(index, row)| {
+            let is_selected = index == self.current_index;
+            row.line()
+                .style(style::Selection::from_is_selected(is_selected))
+        });
+        List::new(lines).render(list_area, buf);
+
+        let status = match &self.status {
+            Status::Idle => Line::default(),
+            Status::Done(text) => {
+                Line::from(text.as_str()).style(Style::new().with_fg(IndexedColor::DarkGreen))
+            }
+            Status::Failed(text) => {
+                Line::from(text.as_str()).style(Style::new().with_fg(IndexedColor::BrightRed))
+            }
+        };
+        Paragraph::new(status).render(status_area, buf);
+
+        let key = |text| Span::raw(text).style(Style::new().with_fg(IndexedColor::DarkYellow));
+        match &self.mode {
+            Mode::Picking | Mode::ConfirmingRunningGame => Line::from(vec![
+                key("Enter"),
+                Span::raw(" - make live and edit  "),
+                key("F2"),
+                Span::raw(" - store the game's folder as a slot  "),
+                key("Esc"),
+                Span::raw(" - exit"),
+            ])
+            .centered()
+            .render(footer_area, buf),
+            Mode::Naming(input) => {
+                let label = "New slot name: ";
+                let [label_area, input_area] = Layout::horizontal([
+                    Constraint::Length(u16::try_from(label.len()).unwrap_or_default()),
+                    Constraint::Fill(1),
+                ])
+                .areas(footer_area);
+                Line::from(label).render(label_area, buf);
+                input.widget(true).render(input_area, buf);
+            }
+        }
+
+        if let Mode::ConfirmingRunningGame = self.mode {
+            Dialog::running_game("the switched slot", "Switch anyway", "Back").render(area, buf);
+        }
+    }
+}
+
+impl Row {
+    fn line(&self) -> Line<'_> {
+        let name = match &self.slot {
+            Some(slot) if self.is_live => format!("{} (live)", slot.name),
+            Some(slot) => slot.name.clone(),
+            None => "Game folder (not in any slot)".to_owned(),
+        };
+        let details = match &self.summary {
+            Ok(Summary {
+                nickname,
+                n_enabled_characters,
+                n_characters,
+                title,
+            }) => format!(
+                "{nickname} | characters: {n_enabled_characters}/{n_characters} | title: {title}"
+            ),
+            Err(error) => format!("can't read: {error}"),
+        };
+        Line::from(format!("{name:<32} {details}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use ratatui::crossterm::event::{Event as RatatuiEvent, KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::{
+        savefile::{
+            backup::{Backups, Retention},
+            slot::Slots,
+            temp_saves::TempSaves,
+        },
+        tui::{Event, HandleEvent},
+    };
+
+    use super::{Outcome, Screen};
+
+    fn press(screen: &mut Screen, code: KeyCode) {
+        let key = RatatuiEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        let event = Event::empty(Instant::now()).follow_with(&key, Instant::now());
+        screen.handle_event(&event);
+    }
+
+    #[rstest::rstest]
+    fn switching_asks_first_while_game_runs() {
+        let saves = TempSaves::new();
+        let env = saves.env();
+        let slots = Slots::new(&env);
+        saves.write_all(b"main");
+        slots.store("main", &env).expect("Precondition: must store");
+        saves.write_all(b"alt");
+        slots.store("alt", &env).expect("Precondition: must store");
+        let mut screen = Screen::new(env, Retention::default());
+        screen.processes = saves.running_game();
+
+        press(&mut screen, KeyCode::Down);
+        press(&mut screen, KeyCode::Enter);
+        assert_eq!(b"alt".as_slice(), saves.read(Backups::FILE_NAMES[0]));
+        assert_eq!(None, screen.outcome());
+
+        press(&mut screen, KeyCode::Char('y'));
+        assert_eq!(b"main".as_slice(), saves.read(Backups::FILE_NAMES[0]));
+        assert_eq!(Some(Outcome::Edit), screen.outcome());
+    }
+}
@@ -1,11 +1,12 @@
 use std::time::{Duration, Instant};
 
 use ratatui::crossterm::event::{Event as RatatuiEvent, KeyEvent};
-use ratatui::crossterm::event::{KeyCode, KeyEventKind};
+use ratatui::crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Event {
     key_code: Option<KeyCode>,
+    modifiers: KeyModifiers,
     ascii_input: AsciiInputBuffer,
     received_at: Instant,
 }
@@ -20,6 +21,7 @@ impl Event {
     pub fn empty(received_at: Instant) -> Self {
         Self {
             key_code: None,
+            modifiers: KeyModifiers::NONE,
             ascii_input: AsciiInputBuffer::empty(),
             received_at,
         }
@@ -28,6 +30,7 @@ impl Event {
     pub fn follow_with(self, event: &RatatuiEvent, now: Instant) -> Self {
         let Self {
             key_code: _,
+            modifiers: _,
             mut ascii_input,
             received_at,
         } = self;
@@ -36,22 +39,28 @@ impl Event {
             ascii_input.clear();
         }
 
-        let key_code = match event {
+        let (key_code, modifiers) = match event {
             &RatatuiEvent::Key(KeyEvent {
                 code,
+                modifiers,
                 kind: KeyEventKind::Press,
                 ..
             }) => {
-                if let KeyCode::Char(c) = code {
+                // Shortcuts are not text.
+                let is_shortcut = modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+                if let KeyCode::Char(c) = code
+                    && !is_shortcut
+                {
                     ascii_input.try_push(c);
                 }
-                Some(code)
+                (Some(code), modifiers)
             }
-            _ => None,
+            _ => (None, KeyModifiers::NONE),
         };
 
         Self {
             key_code,
+            modifiers,
             ascii_input,
             received_at: now,
         }
@@ -61,6 +70,16 @@ impl Event {
         self.key_code
     }
 
+    /// A letter pressed together with `Ctrl`, in lowercase.
+    pub fn ctrl_char(&self) -> Option<char> {
+        match self.key_code {
+            Some(KeyCode::Char(c)) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(c.to_ascii_lowercase())
+            }
+            _ => None,
+        }
+    }
+
     pub fn accumulated_input(&self) -> &str {
         self.ascii_input.as_ref()
     }
@@ -99,9 +118,13 @@ mod tests {
     use super::{AsciiInputBuffer, Event};
 
     fn event_from_key(code: KeyCode) -> RatatuiEvent {
+        event_from_key_with(code, KeyModifiers::NONE)
+    }
+
+    fn event_from_key_with(code: KeyCode, modifiers: KeyModifiers) -> RatatuiEvent {
         RatatuiEvent::Key(KeyEvent {
             code,
-            modifiers: KeyModifiers::NONE,
+            modifiers,
             kind: KeyEventKind::Press,
             state: KeyEventState::empty(),
         })
@@ -150,6 +173,7 @@ mod tests {
 
         let event = Event {
             key_code: None,
+            modifiers: KeyModifiers::NONE,
             // normally i do smth like integration tests with `pub` only but eh, lazy.
             ascii_input: AsciiInputBuffer(initial_value.to_owned()),
             received_at,
@@ -159,4 +183,27 @@ mod tests {
 
         assert_eq!(expected, event.accumulated_input());
     }
+
+    #[rstest::rstest]
+    #[case::ctrl_s(KeyCode::Char('s'), KeyModifiers::CONTROL, Some('s'))]
+    #[case::ctrl_shift_s(
+        KeyCode::Char('S'),
+        KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        Some('s')
+    )]
+    #[case::just_s(KeyCode::Char('s'), KeyModifiers::NONE, None)]
+    #[case::ctrl_enter(KeyCode::Enter, KeyModifiers::CONTROL, None)]
+    fn shortcuts_are_not_text(
+        #[case] code: KeyCode,
+        #[case] modifiers: KeyModifiers,
+        #[case] expected: Option<char>,
+    ) {
+        let event = Event::empty(Instant::now())
+            .follow_with(&event_from_key_with(code, modifiers), Instant::now());
+
+        assert_eq!(expected, event.ctrl_char());
+        if expected.is_some() {
+            assert_eq!("", event.accumulated_input());
+        }
+    }
 }
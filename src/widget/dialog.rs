@@ -0,0 +1,102 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Widget, Wrap},
+};
+
+use crate::style::{IndexedColor, WithColor};
+
+/// A small box drawn on top of everything else, in the middle of the area.
+pub struct Dialog<'a> {
+    title: Line<'a>,
+    lines: Vec<Line<'a>>,
+}
+
+impl<'a> Dialog<'a> {
+    const PADDING: u16 = 4;
+
+    pub fn new(
+        title: impl Into<Line<'a>>,
+        lines: impl IntoIterator<Item = impl Into<Line<'a>>>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            lines: lines.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Asks before writing files that the running game overwrites on exit.
+    ///
+    /// `what` is what gets lost, `proceed` and `cancel` describe `Y` and `N`.
+    pub fn running_game(what: &str, proceed: &str, cancel: &str) -> Self {
+        let key = |text| Span::raw(text).style(Style::new().with_fg(IndexedColor::DarkYellow));
+        Self::new(
+            Line::from("AoS2 is running").bold(),
+            [
+                Line::from("The game writes its own copy of the files when it exits,"),
+                Line::from(format!(
+                    "so {what} is likely to be lost, or to confuse the game."
+                )),
+                Line::from(""),
+                Line::from(vec![
+                    key("Y"),
+                    Span::raw(format!(" - {proceed}  ")),
+                    key("N"),
+                    Span::raw(format!(" - {cancel}")),
+                ]),
+            ],
+        )
+    }
+
+    fn size(&self) -> (u16, u16) {
+        let content_width = self
+            .lines
+            .iter()
+            .chain(std::iter::once(&self.title))
+            .map(Line::width)
+            .max()
+            .unwrap_or_default();
+        let width = u16::try_from(content_width)
+            .unwrap_or(u16::MAX)
+            .saturating_add(Self::PADDING);
+        let height = u16::try_from(self.lines.len())
+            .unwrap_or(u16::MAX)
+            .saturating_add(2);
+        (width, height)
+    }
+}
+
+impl Widget for Dialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let (width, height) = self.size();
+        let [vertical] = Layout::vertical([Constraint::Length(height)])
+            .flex(ratatui::layout::Flex::Center)
+            .areas(area);
+        let [dialog_area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(ratatui::layout::Flex::Center)
+            .areas(vertical);
+
+        let Self { title, lines } = self;
+
+        let block = Block::bordered()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .style(
+                Style::new()
+                    .with_bg(IndexedColor::DarkBlue)
+                    .with_fg(Color::White),
+            );
+
+        Clear.render(dialog_area, buf);
+        Paragraph::new(lines)
+            .centered()
+            .wrap(Wrap { trim: false })
+            .block(block)
+            .render(dialog_area, buf);
+    }
+}
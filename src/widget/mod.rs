@@ -1,5 +1,8 @@
 //! Custom [Widgets][`ratatui::widgets::Widget`].
 
 pub mod content_box;
+pub mod dialog;
+pub mod number_input;
 pub mod sequence;
 pub mod split;
+pub mod text_input;
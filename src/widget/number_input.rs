@@ -0,0 +1,83 @@
+use std::num::ParseIntError;
+
+use ratatui::crossterm::event::KeyCode;
+
+use super::text_input::{TextInput, TextInputWidget};
+
+/// A [`TextInput`] that only takes digits, for counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NumberInput {
+    input: TextInput,
+}
+
+impl NumberInput {
+    pub fn new(value: u32) -> Self {
+        Self {
+            input: TextInput::new(value.to_string()),
+        }
+    }
+
+    /// Fails on empty input or numbers too big for a counter.
+    pub fn value(&self) -> Result<u32, ParseIntError> {
+        self.input.text().parse()
+    }
+
+    pub fn is_cursor_at_end(&self) -> bool {
+        self.input.is_cursor_at_end()
+    }
+
+    pub fn set_value(&mut self, value: u32) {
+        self.input.set_text(value.to_string());
+    }
+
+    /// Returns `true` if the text has changed.
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char(c) if !c.is_ascii_digit() => false,
+            other => self.input.handle_key(other),
+        }
+    }
+
+    pub fn widget(&self, is_active: bool) -> TextInputWidget<'_> {
+        self.input.widget(is_active)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+
+    use super::NumberInput;
+
+    #[rstest::rstest]
+    #[case::digits(0, &[KeyCode::Backspace, KeyCode::Char('4'), KeyCode::Char('2')], Ok(42))]
+    #[case::letters_ignored(1, &[KeyCode::Char('x'), KeyCode::Char('0')], Ok(10))]
+    #[case::empty(7, &[KeyCode::Backspace], Err(()))]
+    #[case::too_big(4_294_967_295, &[KeyCode::Char('0')], Err(()))]
+    fn keys_edit_number(
+        #[case] initial: u32,
+        #[case] keys: &[KeyCode],
+        #[case] expected: Result<u32, ()>,
+    ) {
+        let mut input = NumberInput::new(initial);
+        for &key in keys {
+            input.handle_key(key);
+        }
+
+        assert_eq!(expected, input.value().map_err(|_| ()));
+    }
+
+    #[rstest::rstest]
+    #[case::untouched(&[], true)]
+    #[case::moved_left(&[KeyCode::Left], false)]
+    #[case::moved_back(&[KeyCode::Left, KeyCode::Right], true)]
+    #[case::typed(&[KeyCode::Home, KeyCode::Char('1')], false)]
+    fn cursor_end_is_tracked(#[case] keys: &[KeyCode], #[case] expected: bool) {
+        let mut input = NumberInput::new(42);
+        for &key in keys {
+            input.handle_key(key);
+        }
+
+        assert_eq!(expected, input.is_cursor_at_end());
+    }
+}
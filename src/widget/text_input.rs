@@ -0,0 +1,191 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use crate::style::{IndexedColor, WithColor};
+
+/// A single line of editable text with a cursor.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    text: String,
+    /// Position in characters, not bytes.
+    cursor: usize,
+}
+
+/// How to draw a [`TextInput`].
+pub struct TextInputWidget<'a> {
+    input: &'a TextInput,
+    is_active: bool,
+    is_masked: bool,
+}
+
+impl TextInput {
+    pub const MASK: char = '*';
+
+    /// Starts with the cursor at the end.
+    pub fn new(text: impl Into<String>) -> Self {
+        let text: String = text.into();
+        Self {
+            cursor: text.chars().count(),
+            text,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_cursor_at_end(&self) -> bool {
+        self.cursor == self.len()
+    }
+
+    /// Replaces the text, keeping the cursor where it was, if possible.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.cursor = self.cursor.min(self.len());
+    }
+
+    /// Returns `true` if the text has changed.
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char(c) => {
+                let at = self.byte_offset(self.cursor);
+                self.text.insert(at, c);
+                self.cursor += 1;
+                true
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let at = self.byte_offset(self.cursor);
+                self.text.remove(at);
+                true
+            }
+            KeyCode::Delete if self.cursor < self.len() => {
+                let at = self.byte_offset(self.cursor);
+                self.text.remove(at);
+                true
+            }
+            KeyCode::Left => {
+                self.cursor = self.cursor.saturating_sub(1);
+                false
+            }
+            KeyCode::Right => {
+                self.cursor = (self.cursor + 1).min(self.len());
+                false
+            }
+            KeyCode::Home => {
+                self.cursor = 0;
+                false
+            }
+            KeyCode::End => {
+                self.cursor = self.len();
+                false
+            }
+            _ => false,
+        }
+    }
+
+    pub fn widget(&self, is_active: bool) -> TextInputWidget<'_> {
+        TextInputWidget {
+            input: self,
+            is_active,
+            is_masked: false,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn byte_offset(&self, cursor: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(cursor)
+            .map_or(self.text.len(), |(offset, _)| offset)
+    }
+}
+
+impl TextInputWidget<'_> {
+    /// Hides the text behind [`TextInput::MASK`].
+    pub fn masked(mut self, is_masked: bool) -> Self {
+        self.is_masked = is_masked;
+        self
+    }
+}
+
+impl Widget for TextInputWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let Self {
+            input,
+            is_active,
+            is_masked,
+        } = self;
+
+        let shown: Vec<char> = if is_masked {
+            std::iter::repeat_n(TextInput::MASK, input.len()).collect()
+        } else {
+            input.text.chars().collect()
+        };
+
+        let style = Style::new()
+            .with_bg(IndexedColor::DarkGray)
+            .with_fg(Color::White);
+        let spans = if is_active {
+            let cursor_style = Style::new().bg(Color::White).fg(Color::Black);
+            let before: String = shown[..input.cursor].iter().collect();
+            let under = shown.get(input.cursor).copied().unwrap_or(' ');
+            let after: String = shown.iter().skip(input.cursor + 1).collect();
+            vec![
+                Span::raw(before),
+                Span::raw(under.to_string()).style(cursor_style),
+                Span::raw(after),
+            ]
+        } else {
+            vec![Span::raw(shown.into_iter().collect::<String>())]
+        };
+
+        Line::from(spans).style(style).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+
+    use super::TextInput;
+
+    #[rstest::rstest]
+    #[case::typing("Sug", &[KeyCode::Char('u'), KeyCode::Char('r'), KeyCode::Char('i')], "Suguri")]
+    #[case::backspace("Suguri", &[KeyCode::Backspace, KeyCode::Backspace], "Sugu")]
+    #[case::insert_in_the_middle("Sri", &[KeyCode::Left, KeyCode::Left, KeyCode::Char('u')], "Suri")]
+    #[case::delete_at_start("xSora", &[KeyCode::Home, KeyCode::Delete], "Sora")]
+    #[case::backspace_at_start("Sora", &[KeyCode::Home, KeyCode::Backspace], "Sora")]
+    #[case::delete_at_end("Sora", &[KeyCode::Delete], "Sora")]
+    #[case::non_ascii("Him", &[KeyCode::Char('é')], "Himé")]
+    #[case::after_non_ascii("Hé", &[KeyCode::Char('!'), KeyCode::Left, KeyCode::Backspace], "H!")]
+    fn keys_edit_text(#[case] initial: &str, #[case] keys: &[KeyCode], #[case] expected: &str) {
+        let mut input = TextInput::new(initial);
+        for &key in keys {
+            input.handle_key(key);
+        }
+
+        assert_eq!(expected, input.text());
+    }
+
+    #[rstest::rstest]
+    fn cursor_stays_within_shorter_text() {
+        let mut input = TextInput::new("Star Breaker");
+        input.set_text("Sora");
+        input.handle_key(KeyCode::Char('!'));
+
+        assert_eq!("Sora!", input.text());
+    }
+}